@@ -1,13 +1,77 @@
 mod modules;
-use std::{fs::File, io::BufRead};
+use std::{fs::File, io::BufRead, path::PathBuf};
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use memmap::Mmap;
-use modules::parser::Lexer;
+use modules::{
+    analysis::{
+        archive::ArchiveRun,
+        attribution::{Attribution, DamageReport, PetMode},
+        barswap::BarSwapReport,
+        casts::{CastReport, CastTracker},
+        composition::Composition,
+        debuffs::{BossDebuffReport, DEFAULT_GAP_THRESHOLD_MS},
+        effects::{EffectTimeline, UptimeReport},
+        execute::{ExecuteReport, DEFAULT_EXECUTE_THRESHOLD},
+        gcd::GcdReport,
+        gear::GearReport,
+        interrupts::InterruptReport,
+        loadout::LoadoutReport,
+        passives::PassiveReport,
+        phases::{PhaseConfig, PhaseReport},
+        positions::{PositionReport, PositionTracks},
+        pulls::PullReport,
+        resources::{ResourceReport, ResourceSampler},
+        roles::{role_view, Role},
+        rotation::RotationReport,
+        shields::{ShieldReport, ShieldTracker},
+        sustain::SustainReport,
+        ultimate::UltimateReport,
+        wipe::WipeReport,
+    },
+    catalog::AbilityCatalog,
+    fight::Fight,
+    parser::Lexer,
+    render::{damage_heatmap, movement_traces, ImageFormat},
+};
+
+// Removes `name <value>` from the arguments
+fn take_option(args: &mut Vec<String>, name: &str) -> Result<Option<String>> {
+    let Some(index) = args.iter().position(|a| a == name) else {
+        return Ok(None);
+    };
+    if index + 1 >= args.len() {
+        bail!("{name} needs a value");
+    }
+    let value = args.remove(index + 1);
+    args.remove(index);
+    Ok(Some(value))
+}
 
 fn main() -> Result<()> {
-    let file = File::open("Encounter2.log").unwrap();
-    let mapped_file = unsafe { Mmap::map(&file).unwrap() };
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    // `--role <tank|healer|dps>` limits per-player reports to one role
+    let role = match take_option(&mut args, "--role")? {
+        Some(role) => match Role::parse(&role) {
+            Some(role) => Some(role),
+            None => bail!("{role} is not one of tank, healer or dps"),
+        },
+        None => None,
+    };
+    // `--pets <merge|separate>` credits pets and companions to their owner or lists them apart
+    let pet_mode = match take_option(&mut args, "--pets")? {
+        Some(mode) => match PetMode::parse(&mode) {
+            Some(mode) => mode,
+            None => bail!("{mode} is not one of merge or separate"),
+        },
+        None => PetMode::Merge,
+    };
+    let mut args = args.into_iter();
+    let path = args.next().unwrap_or_else(|| "Encounter2.log".into());
+    let report = args.next();
+
+    let file = File::open(path)?;
+    let mapped_file = unsafe { Mmap::map(&file)? };
     let mut lexer = Lexer::new(mapped_file.lines());
     let mut segment_array = vec![];
     while let Some(segment) = lexer.next_segment() {
@@ -17,10 +81,224 @@ fn main() -> Result<()> {
     drop(mapped_file);
     drop(file);
     println!("Done Parsing: segment array len = {}", segment_array.len());
-    // println!("{:#?}", lexer.data.lines().take(5).collect::<Vec<_>>());
-    // for line in mapped_file.lines().take(20) {
-    //     println!("{}", line?);
-    // }
-    // println!("{file_size}");
+
+    let catalog = AbilityCatalog::new(&segment_array);
+    let mut fights = Fight::split(&segment_array);
+    if let Some(role) = role {
+        fights = fights
+            .iter()
+            .map(|f| role_view(f, &catalog, role))
+            .collect();
+    }
+    match report.as_deref() {
+        Some("uptime") => {
+            let timeline = EffectTimeline::build(&segment_array);
+            for fight in &fights {
+                println!("{}", UptimeReport::new(fight, &timeline, &catalog));
+            }
+        }
+        Some("debuffs") => {
+            let gap_threshold_ms = match args.next() {
+                Some(ms) => ms.parse()?,
+                None => DEFAULT_GAP_THRESHOLD_MS,
+            };
+            let timeline = EffectTimeline::build(&segment_array);
+            for fight in &fights {
+                println!(
+                    "{}",
+                    BossDebuffReport::new(fight, &timeline, &catalog, gap_threshold_ms)
+                );
+            }
+        }
+        Some("casts") => {
+            let tracker = CastTracker::new(&segment_array);
+            for fight in &fights {
+                println!("{}", CastReport::new(fight, &tracker, &catalog));
+            }
+        }
+        Some("interrupts") => {
+            let tracker = CastTracker::new(&segment_array);
+            for fight in &fights {
+                println!("{}", InterruptReport::new(fight, &tracker, &catalog));
+            }
+        }
+        Some("resources") => {
+            let sampler = ResourceSampler::new(&segment_array);
+            for fight in &fights {
+                println!("{}", ResourceReport::new(fight, &sampler));
+            }
+        }
+        Some("ultimate") => {
+            let tracker = CastTracker::new(&segment_array);
+            let sampler = ResourceSampler::new(&segment_array);
+            for fight in &fights {
+                println!(
+                    "{}",
+                    UltimateReport::new(fight, &tracker, &sampler, &catalog)
+                );
+            }
+        }
+        Some("sustain") => {
+            for fight in &fights {
+                println!("{}", SustainReport::new(fight, &catalog));
+            }
+        }
+        Some("shields") => {
+            let tracker = ShieldTracker::new(&segment_array);
+            let sampler = ResourceSampler::new(&segment_array);
+            for fight in &fights {
+                println!("{}", ShieldReport::new(fight, &tracker, &sampler, &catalog));
+            }
+        }
+        Some("phases") => {
+            let mut config = PhaseConfig::default();
+            for arg in args {
+                if config.parse_breakpoints(&arg).is_none() {
+                    bail!("{arg} is not a <monster_id>=<percent>,... breakpoint list");
+                }
+            }
+            let sampler = ResourceSampler::new(&segment_array);
+            for fight in &fights {
+                let attribution = Attribution::new(fight, pet_mode);
+                println!(
+                    "{}",
+                    PhaseReport::new(fight, &attribution, &sampler, &config)
+                );
+            }
+        }
+        Some("execute") => {
+            let threshold = match args.next() {
+                Some(percent) => percent.parse()?,
+                None => DEFAULT_EXECUTE_THRESHOLD,
+            };
+            let tracker = CastTracker::new(&segment_array);
+            let sampler = ResourceSampler::new(&segment_array);
+            for fight in &fights {
+                let attribution = Attribution::new(fight, pet_mode);
+                println!(
+                    "{}",
+                    ExecuteReport::new(
+                        fight,
+                        &attribution,
+                        &tracker,
+                        &sampler,
+                        &catalog,
+                        threshold
+                    )
+                );
+            }
+        }
+        Some("pulls") => {
+            let sampler = ResourceSampler::new(&segment_array);
+            println!("{}", PullReport::new(&fights, &sampler, pet_mode));
+        }
+        Some("wipes") => {
+            let sampler = ResourceSampler::new(&segment_array);
+            for report in fights
+                .iter()
+                .filter_map(|f| WipeReport::new(f, &sampler, &catalog))
+            {
+                println!("{report}");
+            }
+        }
+        Some("rotation") => {
+            // Optional reference rotation, ability names separated by " > "
+            let reference = args.next();
+            let tracker = CastTracker::new(&segment_array);
+            for fight in &fights {
+                println!(
+                    "{}",
+                    RotationReport::new(fight, &tracker, &catalog, reference.as_deref())
+                );
+            }
+        }
+        Some("bars") => {
+            let tracker = CastTracker::new(&segment_array);
+            for fight in &fights {
+                println!("{}", BarSwapReport::new(fight, &tracker, &catalog));
+            }
+        }
+        Some("gcd") => {
+            let tracker = CastTracker::new(&segment_array);
+            for fight in &fights {
+                println!("{}", GcdReport::new(fight, &tracker, &catalog));
+            }
+        }
+        Some("gear") => {
+            for fight in &fights {
+                println!("{}", GearReport::new(fight));
+            }
+        }
+        Some("loadout") => println!("{}", LoadoutReport::new(&segment_array, &fights, &catalog)),
+        Some("passives") => {
+            for fight in &fights {
+                println!("{}", PassiveReport::new(fight, &catalog));
+            }
+        }
+        Some("comp") => {
+            for fight in &fights {
+                println!("{}", Composition::new(fight, &catalog));
+            }
+        }
+        Some("damage") => {
+            for fight in &fights {
+                let attribution = Attribution::new(fight, pet_mode);
+                println!("{}", DamageReport::new(&attribution));
+            }
+        }
+        Some("positions") => {
+            // Optional seconds into each fight to show everyone's position at
+            let snapshot_at = match args.next() {
+                Some(seconds) => Some((seconds.parse::<f32>()? * 1000.0) as usize),
+                None => None,
+            };
+            let sampler = ResourceSampler::new(&segment_array);
+            for fight in &fights {
+                let tracks = PositionTracks::new(fight, &sampler);
+                println!("{}", PositionReport::new(&tracks, snapshot_at));
+            }
+        }
+        Some("archive") => {
+            for run in ArchiveRun::split(&segment_array, &fights, &catalog, pet_mode) {
+                println!("{run}");
+            }
+        }
+        Some("render") => {
+            // `render <traces|heatmap> <directory> [svg|png]`, one image per fight
+            let kind = args.next().unwrap_or_else(|| "traces".into());
+            let directory = PathBuf::from(args.next().unwrap_or_else(|| ".".into()));
+            let format = match args.next() {
+                Some(format) => match ImageFormat::parse(&format) {
+                    Some(format) => format,
+                    None => bail!("{format} is not one of svg or png"),
+                },
+                None => ImageFormat::Svg,
+            };
+            let sampler = ResourceSampler::new(&segment_array);
+            for fight in &fights {
+                let scene = match kind.as_str() {
+                    "traces" => movement_traces(&PositionTracks::new(fight, &sampler), fight),
+                    "heatmap" => damage_heatmap(fight),
+                    x => bail!("{x} is not one of traces or heatmap"),
+                };
+                let path =
+                    directory.join(format!("fight-{}-{kind}.{}", fight.id, format.extension()));
+                scene.save(&path, format)?;
+                println!("Wrote {}", path.display());
+            }
+        }
+        Some(x) => bail!("{x} is not a known report"),
+        None => {
+            println!("{} fights", fights.len());
+            for fight in &fights {
+                println!(
+                    "  {:<4} {:<40} {:>8.1}s",
+                    fight.id,
+                    fight.label(),
+                    fight.duration_ms() as f32 / 1000.0
+                );
+            }
+        }
+    }
     Ok(())
 }
@@ -0,0 +1,382 @@
+use std::{collections::BTreeSet, fmt::Display, ops::Range};
+
+use super::attribution::{Attribution, PetMode};
+use crate::modules::{
+    catalog::AbilityCatalog,
+    fight::{DeathTracker, Fight},
+    log::{Segment, SegmentType},
+};
+
+// Three stages and the cycle boss make a cycle, five cycles an arc
+pub const STAGES_PER_CYCLE: usize = 4;
+pub const CYCLES_PER_ARC: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuffKind {
+    // Picked after a regular stage
+    Verse,
+    // Picked after a cycle boss
+    Vision,
+}
+
+#[derive(Debug)]
+pub struct ArchiveBuff {
+    pub ability_id: usize,
+    pub kind: BuffKind,
+    pub added: usize,
+    pub removed: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct ArchiveStage {
+    // Counted from 1 over the whole run
+    pub number: usize,
+    pub start_time: usize,
+    pub end_time: usize,
+    // False for the stage the run ended in
+    pub cleared: bool,
+    // Group damage to hostile units and time spent in combat
+    pub damage: usize,
+    pub combat_ms: usize,
+    pub deaths: usize,
+}
+
+impl ArchiveStage {
+    pub fn arc(&self) -> usize {
+        (self.number - 1) / (STAGES_PER_CYCLE * CYCLES_PER_ARC) + 1
+    }
+
+    pub fn cycle(&self) -> usize {
+        (self.number - 1) / STAGES_PER_CYCLE % CYCLES_PER_ARC + 1
+    }
+
+    pub fn stage(&self) -> usize {
+        (self.number - 1) % STAGES_PER_CYCLE + 1
+    }
+
+    // Over time in combat, walking between packs is left out
+    pub fn dps(&self) -> f32 {
+        self.damage as f32 / (self.combat_ms.max(1) as f32 / 1000.0)
+    }
+}
+
+// One Infinite Archive run, from ENDLESS_DUNGEON_BEGIN to ENDLESS_DUNGEON_END
+#[derive(Debug)]
+pub struct ArchiveRun<'a> {
+    catalog: &'a AbilityCatalog<'a>,
+    pub id: usize,
+    pub start_time: usize,
+    // From ENDLESS_DUNGEON_END, the logged span for runs without one
+    pub duration_ms: usize,
+    pub stages: Vec<ArchiveStage>,
+    pub buffs: Vec<ArchiveBuff>,
+}
+
+// Segment range of a run with the endless dungeon id its events carried
+struct RunBounds {
+    id: usize,
+    range: Range<usize>,
+    duration_ms: Option<usize>,
+}
+
+impl<'a> ArchiveRun<'a> {
+    pub fn split(
+        segments: &'a [Segment],
+        fights: &[Fight<'a>],
+        catalog: &'a AbilityCatalog<'a>,
+        pet_mode: PetMode,
+    ) -> Vec<ArchiveRun<'a>> {
+        let mut bounds: Vec<RunBounds> = vec![];
+        let mut current: Option<RunBounds> = None;
+        let mut ended = None;
+        // Where a run missing its BEGIN starts, the start of the session or the last run's end
+        let mut open_from = 0;
+        for (index, segment) in segments.iter().enumerate() {
+            let id = match &segment.line {
+                SegmentType::BeginEndlessDungeon(begin) => Some(begin.id),
+                SegmentType::EndEndlessDungeon(end) => Some(end.id),
+                SegmentType::EndlessDungeonBuffAdd(buff)
+                | SegmentType::EndlessDungeonBuffRemove(buff) => Some(buff.id),
+                SegmentType::EndlessDungeonStageEnd(stage) => Some(stage.id),
+                _ => None,
+            };
+            let begin = matches!(&segment.line, SegmentType::BeginEndlessDungeon(_));
+            let other_run = current
+                .as_ref()
+                .is_some_and(|run| id.is_some_and(|id| id != run.id));
+            if begin || other_run || matches!(&segment.line, SegmentType::BeginLog(_)) {
+                bounds.extend(current.take());
+                open_from = index;
+            }
+            // Events of a run without its BEGIN mean logging started mid run
+            if let (None, Some(id)) = (&current, id) {
+                if begin || ended != Some(id) {
+                    current = Some(RunBounds {
+                        id,
+                        range: open_from..index,
+                        duration_ms: None,
+                    });
+                }
+            }
+            if let Some(run) = current.as_mut() {
+                run.range.end = index + 1;
+            }
+            if let SegmentType::EndEndlessDungeon(end) = &segment.line {
+                bounds.extend(current.take().map(|run| RunBounds {
+                    duration_ms: Some(end.duration_ms),
+                    ..run
+                }));
+                ended = Some(end.id);
+                open_from = index + 1;
+            }
+        }
+        bounds.extend(current);
+
+        bounds
+            .into_iter()
+            .map(|run| ArchiveRun::new(run, segments, fights, catalog, pet_mode))
+            .collect()
+    }
+
+    fn new(
+        run: RunBounds,
+        segments: &'a [Segment],
+        fights: &[Fight<'a>],
+        catalog: &'a AbilityCatalog<'a>,
+        pet_mode: PetMode,
+    ) -> Self {
+        let segments = &segments[run.range];
+        let start_time = segments.first().map(|s| s.time).unwrap_or_default();
+        let end_time = segments.last().map(|s| s.time).unwrap_or_default();
+        // Segment index each stage ends at, the run's end closes the last one
+        let mut stage_ends = vec![];
+        let mut buffs: Vec<ArchiveBuff> = vec![];
+        for (index, segment) in segments.iter().enumerate() {
+            match &segment.line {
+                SegmentType::EndlessDungeonStageEnd(_) => stage_ends.push(index),
+                SegmentType::EndlessDungeonBuffAdd(buff) => {
+                    let kind = if !stage_ends.is_empty() && stage_ends.len() % STAGES_PER_CYCLE == 0
+                    {
+                        BuffKind::Vision
+                    } else {
+                        BuffKind::Verse
+                    };
+                    buffs.push(ArchiveBuff {
+                        ability_id: buff.ability_id,
+                        kind,
+                        added: segment.time,
+                        removed: None,
+                    });
+                }
+                SegmentType::EndlessDungeonBuffRemove(buff) => {
+                    if let Some(picked) = buffs
+                        .iter_mut()
+                        .rev()
+                        .find(|b| b.ability_id == buff.ability_id && b.removed.is_none())
+                    {
+                        picked.removed = Some(segment.time);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let cleared = stage_ends.len();
+        // Each stage runs up to and including its STAGE_END
+        let mut ends: Vec<usize> = stage_ends.iter().map(|end| end + 1).collect();
+        let mut starts = vec![0];
+        starts.extend(&ends);
+        ends.push(segments.len());
+        let stages = starts
+            .into_iter()
+            .zip(ends)
+            .enumerate()
+            .filter(|(number, (start, end))| *number < cleared || start < end)
+            .map(|(number, (start, end))| {
+                let stage = &segments[start..end];
+                let range = stage.as_ptr_range();
+                let mut result = ArchiveStage {
+                    number: number + 1,
+                    start_time: stage.first().map_or(end_time, |s| s.time),
+                    end_time: stage.last().map_or(end_time, |s| s.time),
+                    cleared: number < cleared,
+                    damage: 0,
+                    combat_ms: 0,
+                    deaths: 0,
+                };
+                for fight in fights
+                    .iter()
+                    .filter(|f| range.contains(&f.segments.as_ptr()))
+                {
+                    let (boss, adds) = Attribution::new(fight, pet_mode).boss_and_add_damage();
+                    result.damage += boss + adds;
+                    result.combat_ms += fight.duration_ms();
+                    result.deaths += deaths(fight);
+                }
+                result
+            })
+            .collect();
+
+        ArchiveRun {
+            catalog,
+            id: run.id,
+            start_time,
+            duration_ms: run.duration_ms.unwrap_or(end_time - start_time),
+            stages,
+            buffs,
+        }
+    }
+
+    pub fn stages_cleared(&self) -> usize {
+        self.stages.iter().filter(|s| s.cleared).count()
+    }
+
+    pub fn cycles_cleared(&self) -> usize {
+        self.stages_cleared() / STAGES_PER_CYCLE
+    }
+
+    pub fn arcs_cleared(&self) -> usize {
+        self.stages_cleared() / (STAGES_PER_CYCLE * CYCLES_PER_ARC)
+    }
+}
+
+fn deaths(fight: &Fight) -> usize {
+    let group: BTreeSet<usize> = fight.group().map(|p| p.unit_id).collect();
+    let mut tracker = DeathTracker::default();
+    fight
+        .segments
+        .iter()
+        .filter_map(|s| match &s.line {
+            SegmentType::CombatEvent(event) => tracker.observe(event),
+            _ => None,
+        })
+        .filter(|unit_id| group.contains(unit_id))
+        .count()
+}
+
+impl Display for ArchiveRun<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let seconds = |time: usize| (time - self.start_time) as f32 / 1000.0;
+        writeln!(
+            f,
+            "Infinite Archive run {} at {:.1}s, {:.1}s long",
+            self.id,
+            self.start_time as f32 / 1000.0,
+            self.duration_ms as f32 / 1000.0
+        )?;
+        writeln!(
+            f,
+            "  Cleared {} stages, {} cycles, {} arcs",
+            self.stages_cleared(),
+            self.cycles_cleared(),
+            self.arcs_cleared()
+        )?;
+        writeln!(
+            f,
+            "  {:<22} {:>9} {:>9} {:>9} {:>10} {:>7}",
+            "Stage", "Start", "Length", "Combat", "DPS", "Deaths"
+        )?;
+        for stage in &self.stages {
+            let name = format!(
+                "Arc {} Cycle {} Stage {}",
+                stage.arc(),
+                stage.cycle(),
+                stage.stage()
+            );
+            writeln!(
+                f,
+                "  {:<22} {:>8.1}s {:>8.1}s {:>8.1}s {:>10.0} {:>7}",
+                name,
+                seconds(stage.start_time),
+                (stage.end_time - stage.start_time) as f32 / 1000.0,
+                stage.combat_ms as f32 / 1000.0,
+                stage.dps(),
+                stage.deaths
+            )?;
+        }
+        if !self.buffs.is_empty() {
+            writeln!(f, "  Verses and visions")?;
+        }
+        for buff in &self.buffs {
+            write!(
+                f,
+                "    {:>8.1}s {:<7} {}",
+                seconds(buff.added),
+                format!("{:?}", buff.kind),
+                self.catalog.name(buff.ability_id)
+            )?;
+            match buff.removed {
+                Some(removed) => writeln!(f, " (until {:.1}s)", seconds(removed))?,
+                None => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::parser::lex;
+
+    const BEGIN_LOG: &str =
+        "0,BEGIN_LOG,1700000000000,15,\"NA Megaserver\",\"en\",\"eso.live.10.0.0\"\n";
+
+    // (id, start time, length, stages cleared) of each run
+    fn runs(log: &str) -> Vec<(usize, usize, usize, usize)> {
+        let segments = lex(log);
+        let catalog = AbilityCatalog::new(&segments);
+        let fights = Fight::split(&segments);
+        ArchiveRun::split(&segments, &fights, &catalog, PetMode::Merge)
+            .iter()
+            .map(|r| (r.id, r.start_time, r.duration_ms, r.stages_cleared()))
+            .collect()
+    }
+
+    #[test]
+    fn run_missing_begin_starts_with_the_log() {
+        let log = [
+            BEGIN_LOG,
+            "2000,ENDLESS_DUNGEON_STAGE_END,7\n",
+            "5000,ENDLESS_DUNGEON_END,7,9000,F\n",
+        ];
+        assert_eq!(runs(&log.concat()), [(7, 0, 9000, 1)]);
+    }
+
+    #[test]
+    fn other_id_closes_run() {
+        let log = [
+            BEGIN_LOG,
+            "100,ENDLESS_DUNGEON_BEGIN,7,1700000000100,F\n",
+            "1000,ENDLESS_DUNGEON_STAGE_END,7\n",
+            "2000,ENDLESS_DUNGEON_STAGE_END,8\n",
+            "3000,ENDLESS_DUNGEON_END,8,20000,F\n",
+        ];
+        assert_eq!(runs(&log.concat()), [(7, 100, 900, 1), (8, 2000, 20000, 1)]);
+    }
+
+    #[test]
+    fn events_after_end_are_ignored() {
+        let log = [
+            BEGIN_LOG,
+            "100,ENDLESS_DUNGEON_BEGIN,7,1700000000100,F\n",
+            "1000,ENDLESS_DUNGEON_STAGE_END,7\n",
+            "2000,ENDLESS_DUNGEON_END,7,1900,F\n",
+            "2500,ENDLESS_DUNGEON_BUFF_ADDED,7,200\n",
+            "3000,ENDLESS_DUNGEON_STAGE_END,7\n",
+        ];
+        assert_eq!(runs(&log.concat()), [(7, 100, 1900, 1)]);
+    }
+
+    #[test]
+    fn begin_log_closes_run() {
+        let log = [
+            BEGIN_LOG,
+            "100,ENDLESS_DUNGEON_BEGIN,7,1700000000100,F\n",
+            "1000,ENDLESS_DUNGEON_STAGE_END,7\n",
+            BEGIN_LOG,
+            "500,ENDLESS_DUNGEON_STAGE_END,7\n",
+        ];
+        assert_eq!(runs(&log.concat()), [(7, 100, 900, 1), (7, 0, 500, 1)]);
+    }
+}
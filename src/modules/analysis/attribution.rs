@@ -0,0 +1,188 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+use super::damage_done;
+use crate::modules::{combat::PlayerReaction, fight::Fight, log::SegmentType, player::Targets};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PetMode {
+    // Pets and companions are counted as their owner
+    Merge,
+    // Pets and companions get their own rows next to their owner
+    Separate,
+}
+
+impl PetMode {
+    pub fn parse(mode: &str) -> Option<Self> {
+        match mode.to_lowercase().as_str() {
+            "merge" => Some(PetMode::Merge),
+            "separate" => Some(PetMode::Separate),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitKind {
+    Player,
+    Pet,
+    Companion,
+    Other,
+}
+
+// Who owns each pet and companion of the group in a fight, players hidden by the role filter
+// included so group totals stay whole
+#[derive(Debug)]
+pub struct Attribution<'a> {
+    fight: &'a Fight<'a>,
+    pub mode: PetMode,
+    players: BTreeSet<usize>,
+    // unit id -> (owner unit id, pet or companion)
+    owned: BTreeMap<usize, (usize, UnitKind)>,
+}
+
+impl<'a> Attribution<'a> {
+    pub fn new(fight: &'a Fight<'a>, mode: PetMode) -> Self {
+        let players: BTreeSet<usize> = fight.group().map(|p| p.unit_id).collect();
+        let owned = fight
+            .units
+            .values()
+            .filter(|u| players.contains(&u.owner_unit_id))
+            .map(|u| {
+                let kind = match u.reaction {
+                    PlayerReaction::Companion => UnitKind::Companion,
+                    _ => UnitKind::Pet,
+                };
+                (u.unit_id, (u.owner_unit_id, kind))
+            })
+            .collect();
+        Attribution {
+            fight,
+            mode,
+            players,
+            owned,
+        }
+    }
+
+    pub fn owner(&self, unit_id: usize) -> Option<usize> {
+        self.owned.get(&unit_id).map(|(owner, _)| *owner)
+    }
+
+    pub fn kind(&self, unit_id: usize) -> UnitKind {
+        match self.owned.get(&unit_id) {
+            Some((_, kind)) => *kind,
+            None if self.players.contains(&unit_id) => UnitKind::Player,
+            None => UnitKind::Other,
+        }
+    }
+
+    // The unit an event by `unit_id` is credited to, None if it is not part of the group
+    pub fn credit(&self, unit_id: usize) -> Option<usize> {
+        match (self.kind(unit_id), self.mode) {
+            (UnitKind::Player, _) => Some(unit_id),
+            (UnitKind::Pet | UnitKind::Companion, PetMode::Merge) => self.owner(unit_id),
+            (UnitKind::Pet | UnitKind::Companion, PetMode::Separate) => Some(unit_id),
+            (UnitKind::Other, _) => None,
+        }
+    }
+
+    // Re-keys per source totals by credited unit for per-player rows, dropping everything
+    // outside the group and the players hidden by the role filter along with their pets
+    pub fn apply(&self, totals: &BTreeMap<usize, usize>) -> BTreeMap<usize, usize> {
+        let mut credited = BTreeMap::new();
+        for (unit_id, amount) in totals {
+            let Some(unit_id) = self.credit(*unit_id) else {
+                continue;
+            };
+            let player = self.owner(unit_id).unwrap_or(unit_id);
+            if !self.fight.hidden_players.contains(&player) {
+                *credited.entry(unit_id).or_default() += amount;
+            }
+        }
+        credited
+    }
+
+    // Group damage done to (bosses engaged in the fight, everything else hostile)
+    pub fn boss_and_add_damage(&self) -> (usize, usize) {
+        let fight = self.fight;
+        let bosses: BTreeSet<usize> = fight.engaged_bosses().iter().map(|b| b.unit_id).collect();
+        let mut damage = (0, 0);
+        for segment in fight.segments {
+            let SegmentType::CombatEvent(event) = &segment.line else {
+                continue;
+            };
+            if !event.action_result.is_damage() || self.credit(event.source.unit_id()).is_none() {
+                continue;
+            }
+            let Targets::Target(target) = &event.target else {
+                continue;
+            };
+            match fight.units.get(&target.unit_id()) {
+                Some(unit) if bosses.contains(&unit.unit_id) => damage.0 += event.hit_value,
+                Some(unit) if unit.reaction == PlayerReaction::Hostile => {
+                    damage.1 += event.hit_value
+                }
+                _ => (),
+            }
+        }
+        damage
+    }
+
+    pub fn label(&self, unit_id: usize) -> String {
+        let name = self.fight.unit_name(unit_id);
+        match (self.kind(unit_id), self.owner(unit_id)) {
+            (UnitKind::Pet, Some(owner)) => {
+                format!("{} (pet of {})", name, self.fight.unit_name(owner))
+            }
+            (UnitKind::Companion, Some(owner)) => {
+                format!("{} (companion of {})", name, self.fight.unit_name(owner))
+            }
+            _ => name.to_string(),
+        }
+    }
+}
+
+// Damage done to hostile units by every group member, with pets handled by the attribution mode
+#[derive(Debug)]
+pub struct DamageReport<'a> {
+    attribution: &'a Attribution<'a>,
+    pub damage: Vec<(usize, usize)>,
+}
+
+impl<'a> DamageReport<'a> {
+    pub fn new(attribution: &'a Attribution<'a>) -> Self {
+        let fight = attribution.fight;
+        let totals = damage_done(fight.segments, |t| {
+            fight
+                .units
+                .get(&t)
+                .is_some_and(|u| u.reaction == PlayerReaction::Hostile)
+        });
+        let mut damage: Vec<_> = attribution.apply(&totals).into_iter().collect();
+        damage.sort_by_key(|(_, amount)| std::cmp::Reverse(*amount));
+        DamageReport {
+            attribution,
+            damage,
+        }
+    }
+}
+
+impl Display for DamageReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fight = self.attribution.fight;
+        let seconds = fight.duration_ms().max(1) as f32 / 1000.0;
+        writeln!(f, "Fight {} damage done", fight.id)?;
+        for (unit_id, amount) in &self.damage {
+            writeln!(
+                f,
+                "  {:<40} {:>10} {:>9.0} DPS",
+                self.attribution.label(*unit_id),
+                amount,
+                *amount as f32 / seconds
+            )?;
+        }
+        Ok(())
+    }
+}
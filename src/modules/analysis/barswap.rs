@@ -0,0 +1,148 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use super::{casts::CastTracker, percent, rotation::Bar};
+use crate::modules::{
+    abilities::ActionResult, catalog::AbilityCatalog, fight::Fight, log::SegmentType,
+};
+
+#[derive(Debug, Default)]
+pub struct PlayerBars {
+    pub swaps: Vec<usize>,
+    // (time, bar the player moved to), the first entry is the bar the fight started on
+    pub bar_changes: Vec<(usize, Bar)>,
+    pub front_ms: usize,
+    pub back_ms: usize,
+    // (time, ability id) of casts slotted only on the bar the player was not on
+    pub wrong_bar: Vec<(usize, usize)>,
+}
+
+impl PlayerBars {
+    pub fn swaps_per_minute(&self, duration_ms: usize) -> f32 {
+        self.swaps.len() as f32 / (duration_ms.max(1) as f32 / 60_000.0)
+    }
+}
+
+#[derive(Debug)]
+pub struct BarSwapReport<'a> {
+    fight: &'a Fight<'a>,
+    catalog: &'a AbilityCatalog<'a>,
+    pub players: BTreeMap<usize, PlayerBars>,
+}
+
+impl<'a> BarSwapReport<'a> {
+    pub fn new(
+        fight: &'a Fight<'a>,
+        tracker: &CastTracker,
+        catalog: &'a AbilityCatalog<'a>,
+    ) -> Self {
+        let mut players: BTreeMap<usize, PlayerBars> = fight
+            .players()
+            .map(|p| (p.unit_id, PlayerBars::default()))
+            .collect();
+        for segment in fight.segments {
+            let SegmentType::CombatEvent(event) = &segment.line else {
+                continue;
+            };
+            if event.action_result != ActionResult::Weaponswap {
+                continue;
+            }
+            if let Some(player) = players.get_mut(&event.source.unit_id()) {
+                player.swaps.push(segment.time);
+            }
+        }
+
+        for (unit_id, player) in players.iter_mut() {
+            let Some(info) = fight.player_info.get(unit_id) else {
+                continue;
+            };
+            // Only abilities slotted on exactly one bar say anything about the current bar
+            let casts: Vec<(usize, usize, Bar)> = tracker
+                .in_fight(fight)
+                .filter(|c| c.source == *unit_id)
+                .filter_map(|c| {
+                    Bar::of(info, c.ability_id).map(|b| (c.begin_time, c.ability_id, b))
+                })
+                .collect();
+            let Some(&(first_time, _, first_bar)) = casts.first() else {
+                continue;
+            };
+            let swaps_before = player.swaps.iter().filter(|t| **t < first_time).count();
+            let mut bar = if swaps_before % 2 == 0 {
+                first_bar
+            } else {
+                first_bar.other()
+            };
+            player.bar_changes.push((fight.start_time, bar));
+
+            let mut swaps = player.swaps.iter().peekable();
+            for (time, ability_id, slotted) in casts {
+                while let Some(swap) = swaps.next_if(|t| **t <= time) {
+                    bar = bar.other();
+                    player.bar_changes.push((*swap, bar));
+                }
+                if slotted != bar {
+                    // Either a swap went unlogged or the bars changed, follow the cast
+                    player.wrong_bar.push((time, ability_id));
+                    bar = slotted;
+                    player.bar_changes.push((time, bar));
+                }
+            }
+            for swap in swaps {
+                bar = bar.other();
+                player.bar_changes.push((*swap, bar));
+            }
+
+            let ends = player
+                .bar_changes
+                .iter()
+                .skip(1)
+                .map(|(t, _)| *t)
+                .chain([fight.end_time]);
+            for ((start, bar), end) in player.bar_changes.iter().zip(ends) {
+                match bar {
+                    Bar::Front => player.front_ms += end - start,
+                    Bar::Back => player.back_ms += end - start,
+                }
+            }
+        }
+
+        BarSwapReport {
+            fight,
+            catalog,
+            players,
+        }
+    }
+}
+
+impl Display for BarSwapReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Fight {} bar swaps", self.fight.id)?;
+        writeln!(
+            f,
+            "  {:<24} {:>6} {:>9} {:>7} {:>7} {:>9}",
+            "Player", "Swaps", "Swaps/min", "Front", "Back", "Wrong bar"
+        )?;
+        let duration = self.fight.duration_ms();
+        for (unit_id, player) in &self.players {
+            writeln!(
+                f,
+                "  {:<24} {:>6} {:>9.1} {:>6.1}% {:>6.1}% {:>9}",
+                self.fight.unit_name(*unit_id),
+                player.swaps.len(),
+                player.swaps_per_minute(duration),
+                percent(player.front_ms, duration),
+                percent(player.back_ms, duration),
+                player.wrong_bar.len()
+            )?;
+            for (time, ability_id) in &player.wrong_bar {
+                writeln!(
+                    f,
+                    "    {:>7.1}s {}",
+                    (time - self.fight.start_time) as f32 / 1000.0,
+                    self.catalog.name(*ability_id)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::percent;
+use crate::modules::{
+    abilities::{EffectChanged, EndReason},
+    catalog::AbilityCatalog,
+    combat::CombatEvent,
+    fight::Fight,
+    log::{Segment, SegmentType},
+};
+
+#[derive(Debug)]
+pub struct CastEnd {
+    pub time: usize,
+    pub reason: EndReason,
+    pub interrupting_ability_id: Option<usize>,
+    pub interrupting_unit_id: Option<usize>,
+}
+
+// A BEGIN_CAST and everything sharing its cast_track_id
+#[derive(Debug)]
+pub struct Cast<'a> {
+    // Logging session, see Segment::session
+    pub session: usize,
+    pub cast_track_id: usize,
+    pub ability_id: usize,
+    pub source: usize,
+    pub begin_time: usize,
+    pub duration_ms: usize,
+    pub channeled: bool,
+    pub end: Option<CastEnd>,
+    pub events: Vec<(usize, &'a CombatEvent)>,
+    pub effects: Vec<(usize, &'a EffectChanged)>,
+}
+
+impl Cast<'_> {
+    pub fn instant(&self) -> bool {
+        self.duration_ms == 0 && !self.channeled
+    }
+
+    // Instant casts never log an END_CAST, so no end counts as completed for them.
+    // A cast time or channel without one is unknown, e.g. combat ended first
+    pub fn end_reason(&self) -> Option<EndReason> {
+        match &self.end {
+            Some(end) => Some(end.reason),
+            None => self.instant().then_some(EndReason::Completed),
+        }
+    }
+
+    pub fn damage(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|(_, e)| e.action_result.is_damage())
+            .map(|(_, e)| e.hit_value)
+            .sum()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CastTracker<'a> {
+    casts: Vec<Cast<'a>>,
+}
+
+impl<'a> CastTracker<'a> {
+    pub fn new(segments: &'a [Segment]) -> Self {
+        let mut tracker = CastTracker::default();
+        // cast_track_id -> index into casts, ids restart with every BEGIN_LOG
+        let mut by_track_id: HashMap<usize, usize> = HashMap::new();
+        for segment in segments {
+            let time = segment.time;
+            match &segment.line {
+                SegmentType::BeginLog(_) => {
+                    by_track_id.clear();
+                }
+                SegmentType::BeginCast(cast) => {
+                    by_track_id.insert(cast.cast_track_id, tracker.casts.len());
+                    tracker.casts.push(Cast {
+                        session: segment.session,
+                        cast_track_id: cast.cast_track_id,
+                        ability_id: cast.ability_id,
+                        source: cast.source.unit_id(),
+                        begin_time: time,
+                        duration_ms: cast.duration_ms,
+                        channeled: cast.channeled,
+                        end: None,
+                        events: vec![],
+                        effects: vec![],
+                    });
+                }
+                SegmentType::EndCast(end) => {
+                    if let Some(&index) = by_track_id.get(&end.cast_track_id) {
+                        tracker.casts[index].end = Some(CastEnd {
+                            time,
+                            reason: end.end_reason,
+                            interrupting_ability_id: end.interrupting_ability_id,
+                            interrupting_unit_id: end.interrupting_unit_id,
+                        });
+                    }
+                }
+                SegmentType::CombatEvent(event) => {
+                    if let Some(&index) = by_track_id.get(&event.cast_track_id) {
+                        tracker.casts[index].events.push((time, event));
+                    }
+                }
+                SegmentType::EffectChanged(effect) => {
+                    if let Some(&index) = by_track_id.get(&effect.cast_track_id) {
+                        tracker.casts[index].effects.push((time, effect));
+                    }
+                }
+                _ => (),
+            }
+        }
+        tracker
+    }
+
+    // Casts begun while the fight was running, in the order they began
+    pub fn in_fight(&self, fight: &Fight) -> impl Iterator<Item = &Cast<'a>> {
+        let (session, start, end) = (fight.session, fight.start_time, fight.end_time);
+        self.casts
+            .iter()
+            .filter(move |c| c.session == session && (start..=end).contains(&c.begin_time))
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CastStats {
+    pub casts: usize,
+    pub completed: usize,
+    pub cancelled: usize,
+    pub interrupted: usize,
+    pub damage: usize,
+}
+
+#[derive(Debug)]
+pub struct CastReport<'a> {
+    fight: &'a Fight<'a>,
+    catalog: &'a AbilityCatalog<'a>,
+    // (unit id, ability id) -> stats
+    pub stats: Vec<((usize, usize), CastStats)>,
+}
+
+impl<'a> CastReport<'a> {
+    pub fn new(
+        fight: &'a Fight<'a>,
+        tracker: &CastTracker,
+        catalog: &'a AbilityCatalog<'a>,
+    ) -> Self {
+        let mut stats: HashMap<(usize, usize), CastStats> = HashMap::new();
+        for cast in tracker.in_fight(fight) {
+            let stat = stats.entry((cast.source, cast.ability_id)).or_default();
+            stat.casts += 1;
+            stat.damage += cast.damage();
+            match cast.end_reason() {
+                Some(EndReason::Completed) => stat.completed += 1,
+                Some(EndReason::PlayerCancelled) => stat.cancelled += 1,
+                Some(EndReason::Interrupted) => stat.interrupted += 1,
+                None => (),
+            }
+        }
+        let mut stats: Vec<_> = stats.into_iter().collect();
+        stats.sort_by(|a, b| a.0 .0.cmp(&b.0 .0).then(b.1.damage.cmp(&a.1.damage)));
+        CastReport {
+            fight,
+            catalog,
+            stats,
+        }
+    }
+}
+
+impl Display for CastReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Fight {} casts", self.fight.id)?;
+        writeln!(
+            f,
+            "  {:<24} {:<32} {:>6} {:>8} {:>9} {:>11} {:>10}",
+            "Unit", "Ability", "Casts", "Success", "Cancelled", "Interrupted", "Damage"
+        )?;
+        for ((unit_id, ability_id), stat) in &self.stats {
+            writeln!(
+                f,
+                "  {:<24} {:<32} {:>6} {:>7.1}% {:>9} {:>11} {:>10}",
+                self.fight.unit_name(*unit_id),
+                self.catalog.name(*ability_id),
+                stat.casts,
+                percent(stat.completed, stat.casts),
+                stat.cancelled,
+                stat.interrupted,
+                stat.damage
+            )?;
+        }
+        Ok(())
+    }
+}
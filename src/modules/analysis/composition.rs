@@ -0,0 +1,89 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use super::roles::{infer_roles, Role};
+use crate::modules::{catalog::AbilityCatalog, combat::UnitAdded, fight::Fight, player::Class};
+
+#[derive(Debug)]
+pub struct Member<'a> {
+    pub unit: &'a UnitAdded,
+    pub role: Role,
+}
+
+#[derive(Debug)]
+pub struct Composition<'a> {
+    fight: &'a Fight<'a>,
+    pub members: Vec<Member<'a>>,
+}
+
+impl<'a> Composition<'a> {
+    pub fn new(fight: &'a Fight<'a>, catalog: &AbilityCatalog) -> Self {
+        let roles = infer_roles(fight, catalog);
+        let members = fight
+            .players()
+            .map(|unit| Member {
+                unit,
+                role: roles.get(&unit.unit_id).copied().unwrap_or(Role::Dps),
+            })
+            .collect();
+        Composition { fight, members }
+    }
+
+    pub fn roles(&self) -> BTreeMap<Role, usize> {
+        let mut roles = BTreeMap::new();
+        for member in &self.members {
+            *roles.entry(member.role).or_default() += 1;
+        }
+        roles
+    }
+
+    pub fn classes(&self) -> BTreeMap<Class, usize> {
+        let mut classes = BTreeMap::new();
+        for member in &self.members {
+            *classes.entry(member.unit.class).or_default() += 1;
+        }
+        classes
+    }
+
+    pub fn total_cp(&self) -> usize {
+        self.members
+            .iter()
+            .map(|m| m.unit.champion_points as usize)
+            .sum()
+    }
+}
+
+impl Display for Composition<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Fight {} composition", self.fight.id)?;
+        writeln!(
+            f,
+            "  {:<24} {:<20} {:<6} {:<13} {:<10} {:>5}",
+            "Player", "Account", "Role", "Class", "Race", "CP"
+        )?;
+        for member in &self.members {
+            writeln!(
+                f,
+                "  {:<24} {:<20} {:<6} {:<13} {:<10} {:>5}",
+                member.unit.name,
+                member.unit.display_name,
+                format!("{:?}", member.role),
+                format!("{:?}", member.unit.class),
+                format!("{:?}", member.unit.race),
+                member.unit.champion_points
+            )?;
+        }
+        let roles: Vec<_> = self
+            .roles()
+            .iter()
+            .map(|(role, count)| format!("{count} {role:?}"))
+            .collect();
+        let classes: Vec<_> = self
+            .classes()
+            .iter()
+            .map(|(class, count)| format!("{count} {class:?}"))
+            .collect();
+        writeln!(f, "  Roles: {}", roles.join(", "))?;
+        writeln!(f, "  Classes: {}", classes.join(", "))?;
+        writeln!(f, "  Total CP: {}", self.total_cp())
+    }
+}
@@ -0,0 +1,144 @@
+use std::{collections::HashSet, fmt::Display};
+
+use super::{
+    effects::{EffectInterval, EffectTimeline},
+    percent,
+};
+use crate::modules::{catalog::AbilityCatalog, fight::Fight};
+
+// Debuffs supports and tanks are expected to keep on the boss
+pub const KEY_DEBUFFS: [&str; 12] = [
+    "Major Breach",
+    "Minor Breach",
+    "Major Vulnerability",
+    "Minor Vulnerability",
+    "Major Brittle",
+    "Minor Brittle",
+    "Major Cowardice",
+    "Minor Cowardice",
+    "Minor Maim",
+    "Off Balance",
+    "Crusher",
+    "Engulfing Flames",
+];
+
+pub const DEFAULT_GAP_THRESHOLD_MS: usize = 3000;
+
+#[derive(Debug)]
+pub struct DebuffCoverage {
+    pub name: &'static str,
+    pub uptime_ms: usize,
+    // (unit id, uptime) for every group member that applied it
+    pub sources: Vec<(usize, usize)>,
+    // Windows without the debuff longer than the threshold
+    pub gaps: Vec<(usize, usize)>,
+}
+
+#[derive(Debug)]
+pub struct BossDebuffs {
+    pub unit_id: usize,
+    pub debuffs: Vec<DebuffCoverage>,
+}
+
+#[derive(Debug)]
+pub struct BossDebuffReport<'a> {
+    fight: &'a Fight<'a>,
+    pub gap_threshold_ms: usize,
+    pub bosses: Vec<BossDebuffs>,
+}
+
+impl<'a> BossDebuffReport<'a> {
+    pub fn new(
+        fight: &'a Fight<'a>,
+        timeline: &EffectTimeline,
+        catalog: &AbilityCatalog,
+        gap_threshold_ms: usize,
+    ) -> Self {
+        let group_ids: HashSet<usize> = fight.players().map(|p| p.unit_id).collect();
+        let group = &group_ids;
+        let (start, end) = (fight.start_time, fight.end_time);
+        let mut bosses = vec![];
+        for boss in fight.bosses() {
+            let mut debuffs = vec![];
+            for name in KEY_DEBUFFS {
+                let applied = |source: Option<usize>| {
+                    move |i: &EffectInterval| {
+                        i.target == boss.unit_id
+                            && group.contains(&i.source)
+                            && source.is_none_or(|s| s == i.source)
+                            && catalog.name(i.ability_id) == name
+                    }
+                };
+                // Debuffs nobody applied stay listed at 0% with the whole fight as one gap
+                let coverage = timeline.coverage(fight, applied(None));
+                let mut sources: Vec<_> = group
+                    .iter()
+                    .map(|&s| (s, timeline.uptime_ms(fight, applied(Some(s)))))
+                    .filter(|(_, uptime)| *uptime > 0)
+                    .collect();
+                sources.sort_by_key(|s| std::cmp::Reverse(s.1));
+
+                let mut gaps = vec![];
+                let mut last_end = start;
+                for &(s, e) in coverage.iter().chain([(end, end)].iter()) {
+                    if s.saturating_sub(last_end) > gap_threshold_ms {
+                        gaps.push((last_end, s));
+                    }
+                    last_end = e;
+                }
+                debuffs.push(DebuffCoverage {
+                    name,
+                    uptime_ms: coverage.iter().map(|(s, e)| e - s).sum(),
+                    sources,
+                    gaps,
+                });
+            }
+            bosses.push(BossDebuffs {
+                unit_id: boss.unit_id,
+                debuffs,
+            });
+        }
+        BossDebuffReport {
+            fight,
+            gap_threshold_ms,
+            bosses,
+        }
+    }
+}
+
+impl Display for BossDebuffReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let duration = self.fight.duration_ms();
+        writeln!(f, "Fight {} boss debuff coverage", self.fight.id)?;
+        for boss in &self.bosses {
+            writeln!(f, "  {}", self.fight.unit_name(boss.unit_id))?;
+            for debuff in &boss.debuffs {
+                writeln!(
+                    f,
+                    "    {:<24} {:>6.1}%",
+                    debuff.name,
+                    percent(debuff.uptime_ms, duration)
+                )?;
+                for (source, uptime) in &debuff.sources {
+                    writeln!(
+                        f,
+                        "      by {:<21} {:>6.1}%",
+                        self.fight.unit_name(*source),
+                        percent(*uptime, duration)
+                    )?;
+                }
+                for (s, e) in &debuff.gaps {
+                    writeln!(
+                        f,
+                        "      gap {:.1}s - {:.1}s ({:.1}s > {:.1}s)",
+                        (s - self.fight.start_time) as f32 / 1000.0,
+                        (e - self.fight.start_time) as f32 / 1000.0,
+                        (e - s) as f32 / 1000.0,
+                        self.gap_threshold_ms as f32 / 1000.0
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
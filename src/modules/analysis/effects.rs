@@ -0,0 +1,251 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::percent;
+use crate::modules::{
+    abilities::EffectChangeType,
+    catalog::AbilityCatalog,
+    fight::Fight,
+    log::{Segment, SegmentType},
+    player::UnitState,
+};
+
+// One continuous application of an effect by a source on a target
+#[derive(Debug, Clone)]
+pub struct EffectInterval {
+    // Logging session, times and unit ids are only comparable within one
+    pub session: usize,
+    pub ability_id: usize,
+    pub source: usize,
+    pub target: usize,
+    pub start: usize,
+    pub end: usize,
+    // (time, stack count) every time the stack count changed, starting with the gain
+    pub stacks: Vec<(usize, u8)>,
+}
+
+impl EffectInterval {
+    pub fn clip(&self, start: usize, end: usize) -> Option<(usize, usize)> {
+        let clipped = (self.start.max(start), self.end.min(end));
+        (clipped.0 < clipped.1).then_some(clipped)
+    }
+
+    pub fn max_stacks(&self) -> u8 {
+        self.stacks
+            .iter()
+            .map(|(_, s)| *s)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+// Merges overlapping windows, returns them sorted by start time
+pub fn merge_windows(mut windows: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    windows.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = vec![];
+    for (start, end) in windows {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+#[derive(Debug, Default)]
+pub struct EffectTimeline {
+    intervals: Vec<EffectInterval>,
+}
+
+impl EffectTimeline {
+    // Built over the whole log rather than a single fight so effects gained
+    // before BEGIN_COMBAT still count towards the fight they carry into
+    pub fn build(segments: &[Segment]) -> Self {
+        let mut open: HashMap<(usize, usize, usize), EffectInterval> = HashMap::new();
+        let mut unit_added: HashMap<usize, usize> = HashMap::new();
+        let mut intervals = vec![];
+        let mut log_start = segments.first().map(|s| s.time).unwrap_or_default();
+
+        for segment in segments {
+            let time = segment.time;
+            match &segment.line {
+                SegmentType::BeginLog(_) | SegmentType::EndLog => {
+                    intervals.extend(open.drain().map(|(_, mut i)| {
+                        i.end = time;
+                        i
+                    }));
+                    unit_added.clear();
+                    log_start = time;
+                }
+                SegmentType::UnitAdded(unit) => {
+                    unit_added.insert(unit.unit_id, time);
+                }
+                SegmentType::UnitRemoved(unit) => {
+                    let removed: Vec<_> = open
+                        .keys()
+                        .filter(|(target, _, _)| *target == unit.unit_id)
+                        .copied()
+                        .collect();
+                    for key in removed {
+                        let mut interval = open.remove(&key).unwrap();
+                        interval.end = time;
+                        intervals.push(interval);
+                    }
+                }
+                SegmentType::EffectChanged(effect) => {
+                    let Some(target) = effect.target.state(&effect.source).map(UnitState::unit_id)
+                    else {
+                        continue;
+                    };
+                    let key = (target, effect.ability_id, effect.source.unit_id());
+                    // Effects we never saw gained were already active when the unit appeared
+                    let assumed_start = unit_added.get(&target).copied().unwrap_or(log_start);
+                    let start = match effect.change_type {
+                        EffectChangeType::Gained => time,
+                        _ => assumed_start,
+                    };
+                    let interval = open.entry(key).or_insert_with(|| EffectInterval {
+                        session: segment.session,
+                        ability_id: effect.ability_id,
+                        source: effect.source.unit_id(),
+                        target,
+                        start,
+                        end: start,
+                        stacks: vec![(start, effect.stack_count)],
+                    });
+                    if effect.change_type == EffectChangeType::Faded {
+                        let mut interval = open.remove(&key).unwrap();
+                        interval.end = time;
+                        intervals.push(interval);
+                    } else if interval.stacks.last().map(|(_, s)| *s) != Some(effect.stack_count) {
+                        interval.stacks.push((time, effect.stack_count));
+                    }
+                }
+                _ => (),
+            }
+        }
+        let end = segments.last().map(|s| s.time).unwrap_or_default();
+        intervals.extend(open.drain().map(|(_, mut i)| {
+            i.end = end;
+            i
+        }));
+        intervals.sort_by_key(|i| i.start);
+        EffectTimeline { intervals }
+    }
+
+    // Intervals of the fight's logging session overlapping the fight
+    fn in_fight<'b>(&'b self, fight: &Fight) -> impl Iterator<Item = &'b EffectInterval> {
+        let (session, start, end) = (fight.session, fight.start_time, fight.end_time);
+        self.intervals
+            .iter()
+            .filter(move |i| i.session == session && i.clip(start, end).is_some())
+    }
+
+    pub fn on_target<'b>(
+        &'b self,
+        target: usize,
+        fight: &Fight,
+    ) -> impl Iterator<Item = &'b EffectInterval> {
+        self.in_fight(fight).filter(move |i| i.target == target)
+    }
+
+    // Windows where any interval matching the filter was active, clipped to the fight
+    pub fn coverage(
+        &self,
+        fight: &Fight,
+        filter: impl Fn(&EffectInterval) -> bool,
+    ) -> Vec<(usize, usize)> {
+        merge_windows(
+            self.in_fight(fight)
+                .filter(|i| filter(i))
+                .filter_map(|i| i.clip(fight.start_time, fight.end_time))
+                .collect(),
+        )
+    }
+
+    pub fn uptime_ms(&self, fight: &Fight, filter: impl Fn(&EffectInterval) -> bool) -> usize {
+        self.coverage(fight, filter)
+            .iter()
+            .map(|(s, e)| e - s)
+            .sum()
+    }
+}
+
+#[derive(Debug)]
+pub struct Uptime {
+    pub unit_id: usize,
+    pub ability_id: usize,
+    pub uptime_ms: usize,
+    pub max_stacks: u8,
+}
+
+#[derive(Debug)]
+pub struct UptimeReport<'a> {
+    fight: &'a Fight<'a>,
+    catalog: &'a AbilityCatalog<'a>,
+    pub uptimes: Vec<Uptime>,
+}
+
+impl<'a> UptimeReport<'a> {
+    // Uptime of every effect on the group and on the bosses of the fight
+    pub fn new(
+        fight: &'a Fight<'a>,
+        timeline: &EffectTimeline,
+        catalog: &'a AbilityCatalog<'a>,
+    ) -> Self {
+        let mut uptimes = vec![];
+        for unit in fight.players().chain(fight.bosses()) {
+            let mut max_stacks: HashMap<usize, u8> = HashMap::new();
+            for interval in timeline.on_target(unit.unit_id, fight) {
+                let stacks = max_stacks.entry(interval.ability_id).or_default();
+                *stacks = (*stacks).max(interval.max_stacks());
+            }
+            for (ability_id, max_stacks) in max_stacks {
+                let uptime_ms = timeline.uptime_ms(fight, |i| {
+                    i.target == unit.unit_id && i.ability_id == ability_id
+                });
+                uptimes.push(Uptime {
+                    unit_id: unit.unit_id,
+                    ability_id,
+                    uptime_ms,
+                    max_stacks,
+                });
+            }
+        }
+        uptimes.sort_by(|a, b| {
+            a.unit_id
+                .cmp(&b.unit_id)
+                .then(b.uptime_ms.cmp(&a.uptime_ms))
+        });
+        UptimeReport {
+            fight,
+            catalog,
+            uptimes,
+        }
+    }
+
+    pub fn percent(&self, uptime: &Uptime) -> f32 {
+        percent(uptime.uptime_ms, self.fight.duration_ms())
+    }
+}
+
+impl Display for UptimeReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Fight {} ({:.1}s) effect uptime",
+            self.fight.id,
+            self.fight.duration_ms() as f32 / 1000.0
+        )?;
+        for uptime in &self.uptimes {
+            writeln!(
+                f,
+                "  {:<24} {:<36} {:>6.1}% stacks {}",
+                self.fight.unit_name(uptime.unit_id),
+                self.catalog.name(uptime.ability_id),
+                self.percent(uptime),
+                uptime.max_stacks
+            )?;
+        }
+        Ok(())
+    }
+}
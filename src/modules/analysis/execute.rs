@@ -0,0 +1,170 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use super::{
+    attribution::Attribution, casts::CastTracker, phases::BossPhases, resources::ResourceSampler,
+};
+use crate::modules::{catalog::AbilityCatalog, fight::Fight, log::SegmentType, player::UnitState};
+
+pub const DEFAULT_EXECUTE_THRESHOLD: f32 = 25.0;
+
+// Players are expected to start their execute rotation within this long of the threshold
+pub const EXECUTE_SWITCH_GRACE_MS: usize = 3000;
+
+// Abilities that deal bonus damage to low health targets
+pub const EXECUTE_ABILITIES: [&str; 12] = [
+    "Assassin's Blade",
+    "Killer's Blade",
+    "Impale",
+    "Mages' Wrath",
+    "Mages' Fury",
+    "Endless Fury",
+    "Radiant Destruction",
+    "Radiant Glory",
+    "Radiant Oppression",
+    "Reverse Slash",
+    "Reverse Slice",
+    "Executioner",
+];
+
+#[derive(Debug, Default)]
+pub struct PlayerExecute {
+    pub damage_before: usize,
+    pub damage_after: usize,
+    // ability id -> damage dealt to the boss after the threshold
+    pub abilities: BTreeMap<usize, usize>,
+    // First cast of an execute ability after the threshold
+    pub switched_at: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct ExecuteReport<'a> {
+    fight: &'a Fight<'a>,
+    catalog: &'a AbilityCatalog<'a>,
+    pub threshold: f32,
+    pub boss: Option<usize>,
+    pub execute_start: Option<usize>,
+    pub players: BTreeMap<usize, PlayerExecute>,
+}
+
+impl<'a> ExecuteReport<'a> {
+    pub fn new(
+        fight: &'a Fight<'a>,
+        attribution: &Attribution,
+        tracker: &CastTracker,
+        sampler: &ResourceSampler,
+        catalog: &'a AbilityCatalog<'a>,
+        threshold: f32,
+    ) -> Self {
+        let boss = fight
+            .engaged_bosses()
+            .first()
+            .map(|b| BossPhases::new(fight, b.unit_id, sampler, &[]));
+        let execute_start = boss.as_ref().and_then(|b| {
+            b.health
+                .iter()
+                .find(|(_, hp)| *hp < threshold)
+                .map(|(time, _)| *time)
+        });
+        let mut players: BTreeMap<usize, PlayerExecute> = fight
+            .players()
+            .map(|p| (p.unit_id, PlayerExecute::default()))
+            .collect();
+
+        if let (Some(boss), Some(execute_start)) = (&boss, execute_start) {
+            for segment in fight.segments {
+                let SegmentType::CombatEvent(event) = &segment.line else {
+                    continue;
+                };
+                if !event.action_result.is_damage()
+                    || event.target.state(&event.source).map(UnitState::unit_id)
+                        != Some(boss.unit_id)
+                {
+                    continue;
+                }
+                // Pets and companions count for their owner when merged
+                let Some(player) = attribution
+                    .credit(event.source.unit_id())
+                    .and_then(|unit_id| players.get_mut(&unit_id))
+                else {
+                    continue;
+                };
+                if segment.time < execute_start {
+                    player.damage_before += event.hit_value;
+                } else {
+                    player.damage_after += event.hit_value;
+                    *player.abilities.entry(event.ability_id).or_default() += event.hit_value;
+                }
+            }
+
+            for (unit_id, player) in players.iter_mut() {
+                player.switched_at = tracker
+                    .in_fight(fight)
+                    .filter(|c| c.source == *unit_id && c.begin_time >= execute_start)
+                    .find(|c| EXECUTE_ABILITIES.contains(&catalog.name(c.ability_id)))
+                    .map(|c| c.begin_time);
+            }
+        }
+
+        ExecuteReport {
+            fight,
+            catalog,
+            threshold,
+            boss: boss.map(|b| b.unit_id),
+            execute_start,
+            players,
+        }
+    }
+}
+
+impl Display for ExecuteReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (Some(boss), Some(execute_start)) = (self.boss, self.execute_start) else {
+            return writeln!(
+                f,
+                "Fight {} never reached execute <{}%",
+                self.fight.id, self.threshold
+            );
+        };
+        let before = (execute_start - self.fight.start_time).max(1) as f32 / 1000.0;
+        let after = (self.fight.end_time - execute_start).max(1) as f32 / 1000.0;
+        writeln!(
+            f,
+            "Fight {} execute <{}% on {} from {:.1}s",
+            self.fight.id,
+            self.threshold,
+            self.fight.unit_name(boss),
+            before
+        )?;
+        for (unit_id, player) in &self.players {
+            let switch = match player.switched_at {
+                Some(time) if time - execute_start > EXECUTE_SWITCH_GRACE_MS => {
+                    format!(
+                        "late switch +{:.1}s",
+                        (time - execute_start) as f32 / 1000.0
+                    )
+                }
+                Some(time) => format!("switched +{:.1}s", (time - execute_start) as f32 / 1000.0),
+                None => "no execute rotation".into(),
+            };
+            writeln!(
+                f,
+                "  {:<24} {:>9.0} -> {:>9.0} DPS  {}",
+                self.fight.unit_name(*unit_id),
+                player.damage_before as f32 / before,
+                player.damage_after as f32 / after,
+                switch
+            )?;
+            let mut abilities: Vec<_> = player.abilities.iter().collect();
+            abilities.sort_by_key(|(_, damage)| std::cmp::Reverse(**damage));
+            for (ability_id, damage) in abilities {
+                writeln!(
+                    f,
+                    "    {:<32} {:>10}",
+                    self.catalog.name(*ability_id),
+                    damage
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
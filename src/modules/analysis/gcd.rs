@@ -0,0 +1,104 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use super::{casts::CastTracker, percent, rotation::RotationReport};
+use crate::modules::{abilities::EndReason, catalog::AbilityCatalog, fight::Fight};
+
+pub const GCD_MS: usize = 1000;
+
+// Time between two actions longer than this is counted as idle
+pub const IDLE_GAP_MS: usize = 2000;
+
+#[derive(Debug, Default)]
+pub struct GcdUsage {
+    pub windows: usize,
+    // Global cooldown windows in which a skill was cast
+    pub active_windows: usize,
+    pub skills: usize,
+    pub woven: usize,
+    pub light_attacks: usize,
+    pub heavy_attacks: usize,
+    // (start, end) of every gap between actions longer than the idle threshold
+    pub idle: Vec<(usize, usize)>,
+    pub cancelled: usize,
+}
+
+impl GcdUsage {
+    pub fn idle_ms(&self) -> usize {
+        self.idle.iter().map(|(start, end)| end - start).sum()
+    }
+}
+
+#[derive(Debug)]
+pub struct GcdReport<'a> {
+    fight: &'a Fight<'a>,
+    pub players: BTreeMap<usize, GcdUsage>,
+}
+
+impl<'a> GcdReport<'a> {
+    pub fn new(fight: &'a Fight<'a>, tracker: &CastTracker, catalog: &AbilityCatalog) -> Self {
+        let rotations = RotationReport::new(fight, tracker, catalog, None);
+        let windows = fight.duration_ms().div_ceil(GCD_MS);
+        let mut players = BTreeMap::new();
+        for (unit_id, rotation) in rotations.players {
+            let mut usage = GcdUsage {
+                windows,
+                light_attacks: rotation.light_attacks(),
+                ..Default::default()
+            };
+            let mut active = vec![false; windows];
+            for entry in rotation.skills() {
+                if catalog.name(entry.ability_id).starts_with("Heavy Attack") {
+                    usage.heavy_attacks += 1;
+                    continue;
+                }
+                usage.skills += 1;
+                usage.woven += entry.woven as usize;
+                if let Some(window) = active.get_mut((entry.time - fight.start_time) / GCD_MS) {
+                    *window = true;
+                }
+            }
+            usage.active_windows = active.iter().filter(|a| **a).count();
+            usage.idle = rotation
+                .entries
+                .windows(2)
+                .map(|pair| (pair[0].time, pair[1].time))
+                .filter(|(start, end)| end - start > IDLE_GAP_MS)
+                .collect();
+            usage.cancelled = tracker
+                .in_fight(fight)
+                .filter(|c| {
+                    c.source == unit_id && c.end_reason() == Some(EndReason::PlayerCancelled)
+                })
+                .count();
+            players.insert(unit_id, usage);
+        }
+        GcdReport { fight, players }
+    }
+}
+
+impl Display for GcdReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Fight {} global cooldown usage", self.fight.id)?;
+        writeln!(
+            f,
+            "  {:<24} {:>7} {:>7} {:>7} {:>6} {:>6} {:>10} {:>9}",
+            "Player", "GCD", "Skills", "Woven", "LA", "HA", "Idle", "Cancelled"
+        )?;
+        for (unit_id, usage) in &self.players {
+            writeln!(
+                f,
+                "  {:<24} {:>6.1}% {:>7} {:>6.1}% {:>6} {:>6} {:>5} {:>3.0}s {:>9}",
+                self.fight.unit_name(*unit_id),
+                percent(usage.active_windows, usage.windows),
+                usage.skills,
+                percent(usage.woven, usage.skills),
+                usage.light_attacks,
+                usage.heavy_attacks,
+                usage.idle.len(),
+                usage.idle_ms() as f32 / 1000.0,
+                usage.cancelled
+            )?;
+        }
+        Ok(())
+    }
+}
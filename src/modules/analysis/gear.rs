@@ -0,0 +1,117 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use super::sets::SET_NAMES;
+use crate::modules::{
+    fight::Fight,
+    player::{EquipmentLevel, GearPiece, Quality},
+};
+
+pub const MAX_CP_LEVEL: u8 = 160;
+
+pub fn set_name(set_id: usize) -> String {
+    match SET_NAMES.binary_search_by_key(&set_id, |(id, _)| *id) {
+        Ok(index) => SET_NAMES[index].1.to_string(),
+        Err(_) => format!("Set {set_id}"),
+    }
+}
+
+fn below_max_level(level: &EquipmentLevel) -> bool {
+    !matches!(level, EquipmentLevel::Cp(cp) if *cp >= MAX_CP_LEVEL)
+}
+
+fn below_legendary(quality: &Quality) -> bool {
+    matches!(
+        quality,
+        Quality::Normal | Quality::Fine | Quality::Superior | Quality::Epic
+    )
+}
+
+pub fn gear_issues(piece: &GearPiece) -> Vec<&'static str> {
+    let mut issues = vec![];
+    if below_max_level(piece.level) {
+        issues.push("below CP160");
+    }
+    if below_legendary(piece.quality) {
+        issues.push("below Legendary");
+    }
+    match &piece.enchant {
+        Some((_, level, quality)) => {
+            if below_max_level(level) {
+                issues.push("enchant below CP160");
+            }
+            if below_legendary(quality) {
+                issues.push("enchant below Legendary");
+            }
+        }
+        None => issues.push("no enchant"),
+    }
+    issues
+}
+
+#[derive(Debug)]
+pub struct PlayerGear<'a> {
+    pub pieces: Vec<GearPiece<'a>>,
+    // set id -> (pieces counted on the front bar, on the back bar)
+    pub sets: BTreeMap<usize, (usize, usize)>,
+}
+
+#[derive(Debug)]
+pub struct GearReport<'a> {
+    fight: &'a Fight<'a>,
+    pub players: BTreeMap<usize, PlayerGear<'a>>,
+}
+
+impl<'a> GearReport<'a> {
+    pub fn new(fight: &'a Fight<'a>) -> Self {
+        let players = fight
+            .player_info
+            .iter()
+            .map(|(unit_id, info)| {
+                let pieces = info.equipment_info.pieces();
+                let mut sets: BTreeMap<usize, (usize, usize)> = BTreeMap::new();
+                for piece in pieces.iter().filter(|p| p.set_id != 0) {
+                    let (front, back) = sets.entry(piece.set_id).or_default();
+                    if piece.front_bar {
+                        *front += piece.weight;
+                    }
+                    if piece.back_bar {
+                        *back += piece.weight;
+                    }
+                }
+                (*unit_id, PlayerGear { pieces, sets })
+            })
+            .collect();
+        GearReport { fight, players }
+    }
+}
+
+impl Display for GearReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Fight {} gear", self.fight.id)?;
+        for (unit_id, gear) in &self.players {
+            writeln!(f, "  {}", self.fight.unit_name(*unit_id))?;
+            for (set_id, (front, back)) in &gear.sets {
+                writeln!(
+                    f,
+                    "    {:<32} {} front / {} back",
+                    set_name(*set_id),
+                    front,
+                    back
+                )?;
+            }
+            for piece in &gear.pieces {
+                let enchant = piece.enchant.as_ref().map_or("-", |(e, _, _)| e.as_str());
+                let line = format!(
+                    "    {:<16} {:<24} {:<12} {:<24} {}",
+                    piece.slot,
+                    set_name(piece.set_id),
+                    piece.r#trait,
+                    enchant,
+                    gear_issues(piece).join(", ")
+                );
+                writeln!(f, "{}", line.trim_end())?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::casts::{Cast, CastTracker};
+use crate::modules::{
+    abilities::{ActionResult, EndReason},
+    catalog::AbilityCatalog,
+    combat::PlayerReaction,
+    fight::Fight,
+    log::SegmentType,
+    player::UnitState,
+};
+
+pub const CROWD_CONTROL: [ActionResult; 4] = [
+    ActionResult::Stunned,
+    ActionResult::Feared,
+    ActionResult::Knockback,
+    ActionResult::Staggered,
+];
+
+#[derive(Debug, Default)]
+pub struct CrowdControlCount {
+    pub applied: HashMap<ActionResult, usize>,
+    pub received: HashMap<ActionResult, usize>,
+}
+
+#[derive(Debug)]
+pub struct InterruptReport<'a> {
+    fight: &'a Fight<'a>,
+    catalog: &'a AbilityCatalog<'a>,
+    // Every interruptible cast by a hostile unit
+    pub casts: Vec<&'a Cast<'a>>,
+    pub crowd_control: HashMap<usize, CrowdControlCount>,
+}
+
+impl<'a> InterruptReport<'a> {
+    pub fn new(
+        fight: &'a Fight<'a>,
+        tracker: &'a CastTracker<'a>,
+        catalog: &'a AbilityCatalog<'a>,
+    ) -> Self {
+        let casts = tracker
+            .in_fight(fight)
+            .filter(|c| {
+                fight
+                    .units
+                    .get(&c.source)
+                    .is_some_and(|u| u.reaction == PlayerReaction::Hostile)
+                    && catalog
+                        .ability(c.ability_id)
+                        .is_some_and(|a| a.interruptible)
+            })
+            .collect();
+
+        let mut crowd_control: HashMap<usize, CrowdControlCount> = HashMap::new();
+        for segment in fight.segments {
+            let SegmentType::CombatEvent(event) = &segment.line else {
+                continue;
+            };
+            if !CROWD_CONTROL.contains(&event.action_result) {
+                continue;
+            }
+            *crowd_control
+                .entry(event.source.unit_id())
+                .or_default()
+                .applied
+                .entry(event.action_result)
+                .or_default() += 1;
+            if let Some(target) = event.target.state(&event.source).map(UnitState::unit_id) {
+                *crowd_control
+                    .entry(target)
+                    .or_default()
+                    .received
+                    .entry(event.action_result)
+                    .or_default() += 1;
+            }
+        }
+        InterruptReport {
+            fight,
+            catalog,
+            casts,
+            crowd_control,
+        }
+    }
+
+    pub fn interrupted(&self) -> impl Iterator<Item = &&'a Cast<'a>> {
+        self.casts
+            .iter()
+            .filter(|c| c.end_reason() == Some(EndReason::Interrupted))
+    }
+
+    pub fn completed(&self) -> impl Iterator<Item = &&'a Cast<'a>> {
+        self.casts
+            .iter()
+            .filter(|c| c.end_reason() == Some(EndReason::Completed))
+    }
+
+    // Casts with no END_CAST, e.g. the caster died or combat ended mid cast
+    pub fn unknown(&self) -> impl Iterator<Item = &&'a Cast<'a>> {
+        self.casts.iter().filter(|c| c.end_reason().is_none())
+    }
+}
+
+impl Display for InterruptReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Fight {} interruptible casts: {} interrupted, {} completed, {} unknown of {}",
+            self.fight.id,
+            self.interrupted().count(),
+            self.completed().count(),
+            self.unknown().count(),
+            self.casts.len()
+        )?;
+        for cast in &self.casts {
+            let time = (cast.begin_time - self.fight.start_time) as f32 / 1000.0;
+            write!(
+                f,
+                "  {:>7.1}s {:<24} {:<32} ",
+                time,
+                self.fight.unit_name(cast.source),
+                self.catalog.name(cast.ability_id)
+            )?;
+            match &cast.end {
+                Some(end) if end.reason == EndReason::Interrupted => writeln!(
+                    f,
+                    "interrupted after {:.1}s by {} with {}",
+                    (end.time - cast.begin_time) as f32 / 1000.0,
+                    end.interrupting_unit_id
+                        .map_or("Unknown", |u| self.fight.unit_name(u)),
+                    end.interrupting_ability_id
+                        .map_or("Unknown", |a| self.catalog.name(a)),
+                )?,
+                Some(end) => writeln!(f, "{:?}", end.reason)?,
+                None => match cast.end_reason() {
+                    Some(reason) => writeln!(f, "{:?}", reason)?,
+                    None => writeln!(f, "Unknown, no end logged")?,
+                },
+            }
+        }
+        writeln!(f, "  Crowd control (applied / received)")?;
+        for player in self.fight.players() {
+            let Some(count) = self.crowd_control.get(&player.unit_id) else {
+                continue;
+            };
+            write!(f, "    {:<24}", player.name)?;
+            for cc in CROWD_CONTROL {
+                write!(
+                    f,
+                    " {:?} {}/{}",
+                    cc,
+                    count.applied.get(&cc).unwrap_or(&0),
+                    count.received.get(&cc).unwrap_or(&0)
+                )?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
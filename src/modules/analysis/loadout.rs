@@ -0,0 +1,194 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    sync::Arc,
+};
+
+use super::{gear::set_name, rotation::Bar};
+use crate::modules::{
+    catalog::AbilityCatalog,
+    combat::UnitAdded,
+    fight::Fight,
+    log::{Segment, SegmentType},
+    player::{GearPiece, PlayerInfo},
+};
+
+#[derive(Debug)]
+pub enum LoadoutChange {
+    Equipped(&'static str),
+    Unequipped(&'static str),
+    Set(&'static str, usize, usize),
+    Trait(&'static str, String, String),
+    Enchant(&'static str, Option<String>, Option<String>),
+    // (bar, slot index, old ability id, new ability id)
+    Skill(Bar, usize, usize, usize),
+}
+
+// Everything that changed between two PLAYER_INFO of the same player
+#[derive(Debug)]
+pub struct LoadoutDiff {
+    pub time: usize,
+    pub display_name: Arc<str>,
+    // First fight started after the change
+    pub before_fight: Option<usize>,
+    pub changes: Vec<LoadoutChange>,
+}
+
+fn enchant_name(piece: &GearPiece) -> Option<String> {
+    piece.enchant.as_ref().map(|(name, _, _)| name.clone())
+}
+
+pub fn diff(previous: &PlayerInfo, current: &PlayerInfo) -> Vec<LoadoutChange> {
+    let mut changes = vec![];
+    let old: BTreeMap<_, _> = previous
+        .equipment_info
+        .pieces()
+        .into_iter()
+        .map(|p| (p.slot, p))
+        .collect();
+    let new: BTreeMap<_, _> = current
+        .equipment_info
+        .pieces()
+        .into_iter()
+        .map(|p| (p.slot, p))
+        .collect();
+    for (slot, piece) in &new {
+        let Some(was) = old.get(slot) else {
+            changes.push(LoadoutChange::Equipped(slot));
+            continue;
+        };
+        if was.set_id != piece.set_id {
+            changes.push(LoadoutChange::Set(slot, was.set_id, piece.set_id));
+        }
+        if was.r#trait != piece.r#trait {
+            changes.push(LoadoutChange::Trait(
+                slot,
+                was.r#trait.clone(),
+                piece.r#trait.clone(),
+            ));
+        }
+        if enchant_name(was) != enchant_name(piece) {
+            changes.push(LoadoutChange::Enchant(
+                slot,
+                enchant_name(was),
+                enchant_name(piece),
+            ));
+        }
+    }
+    for slot in old.keys().filter(|s| !new.contains_key(*s)) {
+        changes.push(LoadoutChange::Unequipped(slot));
+    }
+
+    let bars = [
+        (
+            Bar::Front,
+            previous.primary_ability_id,
+            current.primary_ability_id,
+        ),
+        (
+            Bar::Back,
+            previous.backup_ability_id,
+            current.backup_ability_id,
+        ),
+    ];
+    for (bar, old, new) in bars {
+        for (slot, (was, is)) in old.into_iter().zip(new).enumerate() {
+            if was != is {
+                changes.push(LoadoutChange::Skill(bar, slot, was, is));
+            }
+        }
+    }
+    changes
+}
+
+#[derive(Debug)]
+pub struct LoadoutReport<'a> {
+    catalog: &'a AbilityCatalog<'a>,
+    pub diffs: Vec<LoadoutDiff>,
+}
+
+impl<'a> LoadoutReport<'a> {
+    // Players are matched by account name, unit ids do not survive a BEGIN_LOG
+    pub fn new(segments: &[Segment], fights: &[Fight], catalog: &'a AbilityCatalog<'a>) -> Self {
+        let mut units: HashMap<usize, &UnitAdded> = HashMap::new();
+        let mut previous: HashMap<Arc<str>, &PlayerInfo> = HashMap::new();
+        let mut diffs = vec![];
+        for segment in segments {
+            match &segment.line {
+                SegmentType::BeginLog(_) => units.clear(),
+                SegmentType::UnitAdded(unit) => {
+                    units.insert(unit.unit_id, unit);
+                }
+                SegmentType::PlayerInfo(info) => {
+                    let Some(unit) = units.get(&info.unit_id) else {
+                        continue;
+                    };
+                    let name = unit.display_name.clone();
+                    if let Some(was) = previous.insert(name.clone(), info) {
+                        let changes = diff(was, info);
+                        if !changes.is_empty() {
+                            diffs.push(LoadoutDiff {
+                                time: segment.time,
+                                display_name: name,
+                                before_fight: fights
+                                    .iter()
+                                    .find(|f| {
+                                        f.session == segment.session && f.start_time >= segment.time
+                                    })
+                                    .map(|f| f.id),
+                                changes,
+                            });
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+        LoadoutReport { catalog, diffs }
+    }
+}
+
+impl Display for LoadoutReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Loadout changes")?;
+        for diff in &self.diffs {
+            write!(
+                f,
+                "  {:>8.1}s {}",
+                diff.time as f32 / 1000.0,
+                diff.display_name
+            )?;
+            match diff.before_fight {
+                Some(fight) => writeln!(f, " before fight {fight}")?,
+                None => writeln!(f)?,
+            }
+            for change in &diff.changes {
+                match change {
+                    LoadoutChange::Equipped(slot) => writeln!(f, "    {slot}: equipped")?,
+                    LoadoutChange::Unequipped(slot) => writeln!(f, "    {slot}: unequipped")?,
+                    LoadoutChange::Set(slot, was, is) => {
+                        writeln!(f, "    {slot}: {} -> {}", set_name(*was), set_name(*is))?
+                    }
+                    LoadoutChange::Trait(slot, was, is) => {
+                        writeln!(f, "    {slot}: trait {was} -> {is}")?
+                    }
+                    LoadoutChange::Enchant(slot, was, is) => writeln!(
+                        f,
+                        "    {slot}: enchant {} -> {}",
+                        was.as_deref().unwrap_or("none"),
+                        is.as_deref().unwrap_or("none")
+                    )?,
+                    LoadoutChange::Skill(bar, slot, was, is) => writeln!(
+                        f,
+                        "    {:?} bar slot {}: {} -> {}",
+                        bar,
+                        slot + 1,
+                        self.catalog.name(*was),
+                        self.catalog.name(*is)
+                    )?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
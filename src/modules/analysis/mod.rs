@@ -0,0 +1,60 @@
+pub mod archive;
+pub mod attribution;
+pub mod barswap;
+pub mod casts;
+pub mod composition;
+pub mod debuffs;
+pub mod effects;
+pub mod execute;
+pub mod gcd;
+pub mod gear;
+pub mod interrupts;
+pub mod loadout;
+pub mod passives;
+pub mod phases;
+pub mod positions;
+pub mod pulls;
+pub mod resources;
+pub mod roles;
+pub mod rotation;
+pub mod sets;
+pub mod shields;
+pub mod sustain;
+pub mod ultimate;
+pub mod wipe;
+
+use std::collections::BTreeMap;
+
+use super::{
+    log::{Segment, SegmentType},
+    player::UnitState,
+};
+
+// Damage dealt by each source unit to the targets matching the filter
+pub fn damage_done(segments: &[Segment], target: impl Fn(usize) -> bool) -> BTreeMap<usize, usize> {
+    let mut damage = BTreeMap::new();
+    for segment in segments {
+        let SegmentType::CombatEvent(event) = &segment.line else {
+            continue;
+        };
+        if !event.action_result.is_damage() {
+            continue;
+        }
+        if event
+            .target
+            .state(&event.source)
+            .map(UnitState::unit_id)
+            .is_some_and(&target)
+        {
+            *damage.entry(event.source.unit_id()).or_default() += event.hit_value;
+        }
+    }
+    damage
+}
+
+pub fn percent(part: usize, whole: usize) -> f32 {
+    if whole == 0 {
+        return 0.0;
+    }
+    part as f32 / whole as f32 * 100.0
+}
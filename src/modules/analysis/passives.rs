@@ -0,0 +1,160 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use super::gear::set_name;
+use crate::modules::{catalog::AbilityCatalog, fight::Fight};
+
+// Buffs granted by the more common food and drink, generic ones are matched by prefix instead
+pub const FOOD_BUFFS: [&str; 10] = [
+    "Artaeum Takeaway Broth",
+    "Bewitched Sugar Skulls",
+    "Clockwork Citrus Filet",
+    "Dubious Camoran Throne",
+    "Ghastly Eye Bowl",
+    "Jewels of Misrule",
+    "Lava Foot Soup-and-Saltrice",
+    "Orzorga's Red Frothgar",
+    "Orzorga's Smoked Bear Haunch",
+    "Witchmother's Potent Brew",
+];
+pub const FOOD_PREFIXES: [&str; 2] = ["Increase Max ", "Crown "];
+
+pub const CHAMPION_PASSIVES: [&str; 16] = [
+    "Arcane Supremacy",
+    "Backstabber",
+    "Biting Aura",
+    "Deadly Aim",
+    "Enlivening Overflow",
+    "Exploiter",
+    "Fighting Finesse",
+    "From the Brink",
+    "Hope Infusion",
+    "Ironclad",
+    "Master-at-Arms",
+    "Rejuvenator",
+    "Steeled Fortitude",
+    "Thaumaturge",
+    "Untamed Aggression",
+    "Wrathful Strikes",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PassiveKind {
+    Mundus,
+    Food,
+    ChampionPoint,
+    Vampire,
+    Werewolf,
+    // Bonuses of the sets the player has equipped
+    Set,
+    // Skill passives and anything else we do not recognise
+    Other,
+}
+
+impl PassiveKind {
+    // Set bonuses carry the name of their set, perfected sets share the bonus of the normal one
+    pub fn classify(name: &str, sets: &[String]) -> Self {
+        if name.starts_with("Boon: ") {
+            PassiveKind::Mundus
+        } else if FOOD_BUFFS.contains(&name) || FOOD_PREFIXES.iter().any(|p| name.starts_with(p)) {
+            PassiveKind::Food
+        } else if CHAMPION_PASSIVES.contains(&name) {
+            PassiveKind::ChampionPoint
+        } else if name.contains("Vampirism") {
+            PassiveKind::Vampire
+        } else if name.contains("Lycanthropy") {
+            PassiveKind::Werewolf
+        } else if sets
+            .iter()
+            .any(|set| set.strip_prefix("Perfected ").unwrap_or(set) == name)
+        {
+            PassiveKind::Set
+        } else {
+            PassiveKind::Other
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PassiveSnapshot {
+    // kind -> (ability id, stack count)
+    pub effects: BTreeMap<PassiveKind, Vec<(usize, u8)>>,
+}
+
+impl PassiveSnapshot {
+    pub fn has(&self, kind: PassiveKind) -> bool {
+        self.effects.contains_key(&kind)
+    }
+
+    pub fn warnings(&self) -> Vec<&'static str> {
+        let mut warnings = vec![];
+        if !self.has(PassiveKind::Food) {
+            warnings.push("no food buff active");
+        }
+        if !self.has(PassiveKind::Mundus) {
+            warnings.push("no mundus stone");
+        }
+        warnings
+    }
+}
+
+#[derive(Debug)]
+pub struct PassiveReport<'a> {
+    fight: &'a Fight<'a>,
+    catalog: &'a AbilityCatalog<'a>,
+    pub players: BTreeMap<usize, PassiveSnapshot>,
+}
+
+impl<'a> PassiveReport<'a> {
+    pub fn new(fight: &'a Fight<'a>, catalog: &'a AbilityCatalog<'a>) -> Self {
+        let players = fight
+            .player_info
+            .iter()
+            .map(|(unit_id, info)| {
+                let mut snapshot = PassiveSnapshot::default();
+                let sets: Vec<String> = info
+                    .equipment_info
+                    .pieces()
+                    .iter()
+                    .map(|p| set_name(p.set_id))
+                    .collect();
+                for effect in &info.long_term_effect {
+                    let kind = PassiveKind::classify(catalog.name(effect.ability_id), &sets);
+                    snapshot
+                        .effects
+                        .entry(kind)
+                        .or_default()
+                        .push((effect.ability_id, effect.stack_count));
+                }
+                (*unit_id, snapshot)
+            })
+            .collect();
+        PassiveReport {
+            fight,
+            catalog,
+            players,
+        }
+    }
+}
+
+impl Display for PassiveReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Fight {} long term effects", self.fight.id)?;
+        for (unit_id, snapshot) in &self.players {
+            writeln!(f, "  {}", self.fight.unit_name(*unit_id))?;
+            for warning in snapshot.warnings() {
+                writeln!(f, "    ! {warning}")?;
+            }
+            for (kind, effects) in &snapshot.effects {
+                let names: Vec<_> = effects
+                    .iter()
+                    .map(|(ability_id, stacks)| match stacks {
+                        0 | 1 => self.catalog.name(*ability_id).to_string(),
+                        stacks => format!("{} x{}", self.catalog.name(*ability_id), stacks),
+                    })
+                    .collect();
+                writeln!(f, "    {:<14} {}", format!("{kind:?}"), names.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,207 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::{attribution::Attribution, damage_done, resources::ResourceSampler};
+use crate::modules::{abilities::ActionResult, fight::Fight, log::SegmentType, player::UnitState};
+
+pub const EXECUTE_THRESHOLDS: [f32; 2] = [25.0, 20.0];
+
+// Immune hits further apart than this belong to separate invulnerability windows
+pub const INVULNERABLE_GAP_MS: usize = 2000;
+
+// Health percentages at which each boss moves to its next phase
+#[derive(Debug, Default)]
+pub struct PhaseConfig {
+    pub breakpoints: HashMap<usize, Vec<f32>>,
+}
+
+impl PhaseConfig {
+    // Parses `<monster_id>=<percent>,<percent>,...`
+    pub fn parse_breakpoints(&mut self, arg: &str) -> Option<()> {
+        let (monster_id, percentages) = arg.split_once('=')?;
+        let mut percentages = percentages
+            .split(',')
+            .map(|p| p.parse().ok())
+            .collect::<Option<Vec<f32>>>()?;
+        percentages.sort_by(|a, b| b.total_cmp(a));
+        self.breakpoints
+            .insert(monster_id.parse().ok()?, percentages);
+        Some(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Phase {
+    pub index: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug)]
+pub struct BossPhases {
+    pub unit_id: usize,
+    // (time, health %) from the boss as target of every combat event
+    pub health: Vec<(usize, f32)>,
+    pub phases: Vec<Phase>,
+    pub invulnerable: Vec<(usize, usize)>,
+    // (threshold, time the boss first dropped below it)
+    pub execute: Vec<(f32, usize)>,
+}
+
+impl BossPhases {
+    pub fn new(
+        fight: &Fight,
+        unit_id: usize,
+        sampler: &ResourceSampler,
+        breakpoints: &[f32],
+    ) -> Self {
+        let health = sampler.health_percent(fight, unit_id);
+        let first_below = |threshold: f32| {
+            health
+                .iter()
+                .find(|(_, hp)| *hp < threshold)
+                .map(|(time, _)| *time)
+        };
+
+        let mut phases = vec![];
+        let mut start = fight.start_time;
+        for &breakpoint in breakpoints {
+            let Some(end) = health
+                .iter()
+                .find(|(time, hp)| *time >= start && *hp <= breakpoint)
+                .map(|(time, _)| *time)
+            else {
+                break;
+            };
+            phases.push(Phase {
+                index: phases.len(),
+                start,
+                end,
+            });
+            start = end;
+        }
+        phases.push(Phase {
+            index: phases.len(),
+            start,
+            end: fight.end_time,
+        });
+
+        let mut invulnerable: Vec<(usize, usize)> = vec![];
+        for segment in fight.segments {
+            let SegmentType::CombatEvent(event) = &segment.line else {
+                continue;
+            };
+            if event.action_result != ActionResult::Immune
+                || event.target.state(&event.source).map(UnitState::unit_id) != Some(unit_id)
+            {
+                continue;
+            }
+            match invulnerable.last_mut() {
+                Some(window) if segment.time - window.1 <= INVULNERABLE_GAP_MS => {
+                    window.1 = segment.time
+                }
+                _ => invulnerable.push((segment.time, segment.time)),
+            }
+        }
+
+        BossPhases {
+            unit_id,
+            execute: EXECUTE_THRESHOLDS
+                .iter()
+                .filter_map(|&t| first_below(t).map(|time| (t, time)))
+                .collect(),
+            health,
+            phases,
+            invulnerable,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PhaseReport<'a> {
+    fight: &'a Fight<'a>,
+    attribution: &'a Attribution<'a>,
+    pub bosses: Vec<BossPhases>,
+}
+
+impl<'a> PhaseReport<'a> {
+    pub fn new(
+        fight: &'a Fight<'a>,
+        attribution: &'a Attribution<'a>,
+        sampler: &ResourceSampler,
+        config: &PhaseConfig,
+    ) -> Self {
+        let bosses = fight
+            .bosses()
+            .map(|boss| {
+                let breakpoints = config
+                    .breakpoints
+                    .get(&boss.monster_id)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+                BossPhases::new(fight, boss.unit_id, sampler, breakpoints)
+            })
+            .collect();
+        PhaseReport {
+            fight,
+            attribution,
+            bosses,
+        }
+    }
+}
+
+impl Display for PhaseReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let relative = |time: usize| (time - self.fight.start_time) as f32 / 1000.0;
+        writeln!(f, "Fight {} phases", self.fight.id)?;
+        for boss in &self.bosses {
+            writeln!(f, "  {}", self.fight.unit_name(boss.unit_id))?;
+            let mut windows: Vec<_> = boss
+                .phases
+                .iter()
+                .map(|p| (format!("Phase {}", p.index + 1), p.start, p.end))
+                .collect();
+            windows.extend(boss.execute.iter().map(|(threshold, start)| {
+                (
+                    format!("Execute <{threshold}%"),
+                    *start,
+                    self.fight.end_time,
+                )
+            }));
+            for (name, start, end) in windows {
+                let phase = self.fight.slice(start, end);
+                writeln!(
+                    f,
+                    "    {:<14} {:>7.1}s - {:>7.1}s",
+                    name,
+                    relative(start),
+                    relative(end)
+                )?;
+                // Skipped straight past the breakpoint
+                if phase.duration_ms() == 0 {
+                    continue;
+                }
+                let seconds = phase.duration_ms() as f32 / 1000.0;
+                let damage = damage_done(phase.segments, |t| {
+                    self.fight.units.get(&t).is_some_and(|u| u.is_boss)
+                });
+                for (unit_id, damage) in self.attribution.apply(&damage) {
+                    writeln!(
+                        f,
+                        "      {:<40} {:>10.0} DPS",
+                        self.attribution.label(unit_id),
+                        damage as f32 / seconds
+                    )?;
+                }
+            }
+            for (start, end) in &boss.invulnerable {
+                writeln!(
+                    f,
+                    "    Invulnerable   {:>7.1}s - {:>7.1}s",
+                    relative(*start),
+                    relative(*end)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,203 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use super::resources::ResourceSampler;
+use crate::modules::{fight::Fight, player::UnitState};
+
+// Spacing of the samples distance and spread metrics are averaged over
+pub const POSITION_STEP_MS: usize = 1000;
+
+// Normalized map coordinates, distances are in fractions of the map
+#[derive(Debug, Clone, Copy)]
+pub struct Position {
+    pub time: usize,
+    pub x: f32,
+    pub y: f32,
+    pub heading: f32,
+}
+
+impl Position {
+    // Units without a known position are logged at 0, 0
+    fn of(time: usize, state: &UnitState) -> Option<Self> {
+        let (x, y) = state.position();
+        (x != 0.0 || y != 0.0).then_some(Position {
+            time,
+            x,
+            y,
+            heading: state.heading(),
+        })
+    }
+
+    pub fn distance(&self, other: &Position) -> f32 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+// Positions of the units of one fight, read from the states in the resource sampler
+#[derive(Debug)]
+pub struct PositionTracks<'a> {
+    fight: &'a Fight<'a>,
+    sampler: &'a ResourceSampler<'a>,
+}
+
+impl<'a> PositionTracks<'a> {
+    pub fn new(fight: &'a Fight<'a>, sampler: &'a ResourceSampler<'a>) -> Self {
+        PositionTracks { fight, sampler }
+    }
+
+    pub fn track(&self, unit_id: usize) -> Vec<Position> {
+        self.sampler
+            .states(self.fight, unit_id)
+            .iter()
+            .filter(|(time, _)| (self.fight.start_time..=self.fight.end_time).contains(time))
+            .filter_map(|(time, state)| Position::of(*time, state))
+            .collect()
+    }
+
+    // Last known position at or before the time
+    pub fn at(&self, unit_id: usize, time: usize) -> Option<Position> {
+        let (seen, state) = self.sampler.state_at(self.fight, unit_id, time)?;
+        Position::of(seen, state)
+    }
+
+    // Where every player and boss was at the time
+    pub fn snapshot(&self, time: usize) -> BTreeMap<usize, Position> {
+        self.fight
+            .players()
+            .chain(self.fight.bosses())
+            .filter_map(|u| self.at(u.unit_id, time).map(|p| (u.unit_id, p)))
+            .collect()
+    }
+
+    pub fn distance(&self, unit_id: usize, other: usize, time: usize) -> Option<f32> {
+        Some(self.at(unit_id, time)?.distance(&self.at(other, time)?))
+    }
+
+    // Mean distance of the players from their centre
+    pub fn spread(&self, time: usize) -> Option<f32> {
+        let positions: Vec<Position> = self
+            .fight
+            .players()
+            .filter_map(|p| self.at(p.unit_id, time))
+            .collect();
+        if positions.is_empty() {
+            return None;
+        }
+        let count = positions.len() as f32;
+        let centre = Position {
+            time,
+            x: positions.iter().map(|p| p.x).sum::<f32>() / count,
+            y: positions.iter().map(|p| p.y).sum::<f32>() / count,
+            heading: 0.0,
+        };
+        Some(positions.iter().map(|p| p.distance(&centre)).sum::<f32>() / count)
+    }
+
+    pub fn sample_times(&self) -> impl Iterator<Item = usize> {
+        (self.fight.start_time..=self.fight.end_time).step_by(POSITION_STEP_MS)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Distance {
+    pub average: f32,
+    pub max: f32,
+}
+
+impl Distance {
+    fn over(distances: impl Iterator<Item = f32>) -> Option<Self> {
+        let (mut sum, mut count, mut max) = (0.0, 0, 0.0_f32);
+        for distance in distances {
+            sum += distance;
+            count += 1;
+            max = max.max(distance);
+        }
+        (count > 0).then(|| Distance {
+            average: sum / count as f32,
+            max,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct PositionReport<'a> {
+    tracks: &'a PositionTracks<'a>,
+    pub boss: Option<usize>,
+    pub to_boss: BTreeMap<usize, Distance>,
+    pub spread: Option<Distance>,
+    // Time and positions of the requested snapshot
+    pub snapshot: Option<(usize, BTreeMap<usize, Position>)>,
+}
+
+impl<'a> PositionReport<'a> {
+    pub fn new(tracks: &'a PositionTracks<'a>, snapshot_at: Option<usize>) -> Self {
+        let fight = tracks.fight;
+        let boss = fight.engaged_bosses().first().map(|b| b.unit_id);
+        let to_boss = match boss {
+            Some(boss) => fight
+                .players()
+                .filter_map(|p| {
+                    let distances = tracks
+                        .sample_times()
+                        .filter_map(|time| tracks.distance(p.unit_id, boss, time));
+                    Distance::over(distances).map(|d| (p.unit_id, d))
+                })
+                .collect(),
+            None => BTreeMap::new(),
+        };
+        PositionReport {
+            tracks,
+            boss,
+            to_boss,
+            spread: Distance::over(tracks.sample_times().filter_map(|t| tracks.spread(t))),
+            snapshot: snapshot_at.map(|offset| {
+                let time = fight.start_time + offset;
+                (time, tracks.snapshot(time))
+            }),
+        }
+    }
+}
+
+impl Display for PositionReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fight = self.tracks.fight;
+        writeln!(f, "Fight {} positioning", fight.id)?;
+        if let Some(boss) = self.boss {
+            writeln!(f, "  Distance to {}", fight.unit_name(boss))?;
+            for (unit_id, distance) in &self.to_boss {
+                writeln!(
+                    f,
+                    "    {:<24} {:>8.4} avg {:>8.4} max",
+                    fight.unit_name(*unit_id),
+                    distance.average,
+                    distance.max
+                )?;
+            }
+        }
+        if let Some(spread) = &self.spread {
+            writeln!(
+                f,
+                "  Group spread {:>8.4} avg {:>8.4} max",
+                spread.average, spread.max
+            )?;
+        }
+        if let Some((time, positions)) = &self.snapshot {
+            writeln!(
+                f,
+                "  Positions at {:.1}s",
+                (time - fight.start_time) as f32 / 1000.0
+            )?;
+            for (unit_id, position) in positions {
+                writeln!(
+                    f,
+                    "    {:<24} {:>7.4} {:>7.4} {:>6.2} rad  seen {:.1}s",
+                    fight.unit_name(*unit_id),
+                    position.x,
+                    position.y,
+                    position.heading,
+                    (position.time as f32 - fight.start_time as f32) / 1000.0
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use super::{
+    attribution::{Attribution, PetMode},
+    resources::ResourceSampler,
+};
+use crate::modules::fight::{Fight, Outcome};
+
+// Spacing of the boss health timeline in the report
+pub const HEALTH_TIMELINE_STEP_MS: usize = 10_000;
+
+// Side by side comparison of every pull in the log
+#[derive(Debug)]
+pub struct PullReport<'a> {
+    pub fights: &'a [Fight<'a>],
+    sampler: &'a ResourceSampler<'a>,
+    pet_mode: PetMode,
+}
+
+impl<'a> PullReport<'a> {
+    pub fn new(
+        fights: &'a [Fight<'a>],
+        sampler: &'a ResourceSampler<'a>,
+        pet_mode: PetMode,
+    ) -> Self {
+        PullReport {
+            fights,
+            sampler,
+            pet_mode,
+        }
+    }
+}
+
+fn health_at(samples: &[(usize, f32)], time: usize) -> Option<f32> {
+    let index = samples.partition_point(|(t, _)| *t <= time);
+    index.checked_sub(1).map(|i| samples[i].1)
+}
+
+impl Display for PullReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:<6} {:<40} {:>9} {:<8} {:>9} {:>9} {:>10} {:>10}",
+            "Fight", "Pull", "Duration", "Outcome", "TTK", "Boss HP", "Boss DPS", "Adds DPS"
+        )?;
+        for fight in self.fights {
+            let bosses = fight.engaged_bosses();
+            if bosses.is_empty() {
+                continue;
+            }
+            let seconds = fight.duration_ms().max(1) as f32 / 1000.0;
+            let health: BTreeMap<usize, Vec<(usize, f32)>> = bosses
+                .iter()
+                .map(|b| (b.unit_id, self.sampler.health_percent(fight, b.unit_id)))
+                .collect();
+            let outcome = match fight.outcome() {
+                Outcome::Kill(_) => "Kill",
+                Outcome::Wipe => "Wipe",
+                Outcome::Reset => "Reset",
+            };
+            let lowest = health
+                .values()
+                .filter_map(|s| s.last().map(|(_, hp)| *hp))
+                .fold(100.0, f32::min);
+            let (boss, adds) = Attribution::new(fight, self.pet_mode).boss_and_add_damage();
+            writeln!(
+                f,
+                "{:<6} {:<40} {:>8.1}s {:<8} {:>9} {:>8.1}% {:>10.0} {:>10.0}",
+                fight.id,
+                fight.label(),
+                seconds,
+                outcome,
+                fight
+                    .time_to_kill()
+                    .map_or("-".into(), |ttk| format!("{:.1}s", ttk as f32 / 1000.0)),
+                lowest,
+                boss as f32 / seconds,
+                adds as f32 / seconds
+            )?;
+            for (unit_id, samples) in &health {
+                write!(f, "       {:<24}", fight.unit_name(*unit_id))?;
+                for time in (fight.start_time..=fight.end_time).step_by(HEALTH_TIMELINE_STEP_MS) {
+                    match health_at(samples, time) {
+                        Some(hp) => write!(f, " {:>5.1}", hp)?,
+                        None => write!(f, " {:>5}", "-")?,
+                    }
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+use std::{collections::HashMap, fmt::Display};
+
+use super::percent;
+use crate::modules::{
+    fight::Fight,
+    log::{Segment, SegmentType},
+    player::{Targets, UnitState},
+};
+
+pub const LOW_RESOURCE_FRACTION: f32 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resource {
+    Health,
+    Magicka,
+    Stamina,
+    Ultimate,
+    Werewolf,
+    Shield,
+}
+
+impl Resource {
+    // (current, max), shields have no maximum so they are measured against max health
+    pub fn of(&self, state: &UnitState) -> (usize, usize) {
+        match self {
+            Self::Health => state.health(),
+            Self::Magicka => state.magicka(),
+            Self::Stamina => state.stamina(),
+            Self::Ultimate => (state.ultimate().0.into(), state.ultimate().1.into()),
+            Self::Werewolf => (state.werewolf().0.into(), state.werewolf().1.into()),
+            Self::Shield => (state.shield(), state.health().1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub time: usize,
+    pub current: usize,
+    pub max: usize,
+}
+
+// Every UnitState recorded for each unit, as source or target of an event
+#[derive(Debug, Default)]
+pub struct ResourceSampler<'a> {
+    // Keyed by (session, unit id), unit ids and times restart with every BEGIN_LOG
+    states: HashMap<(usize, usize), Vec<(usize, &'a UnitState)>>,
+}
+
+impl<'a> ResourceSampler<'a> {
+    pub fn new(segments: &'a [Segment]) -> Self {
+        let mut sampler = ResourceSampler::default();
+        for segment in segments {
+            let (source, target) = match &segment.line {
+                SegmentType::BeginCast(cast) => (&cast.source, Some(&cast.target)),
+                SegmentType::CombatEvent(event) => (&event.source, Some(&event.target)),
+                SegmentType::EffectChanged(effect) => (&effect.source, Some(&effect.target)),
+                SegmentType::HealthRegen(regen) => (&regen.source, None),
+                _ => continue,
+            };
+            sampler.push(segment, source);
+            if let Some(Targets::Target(target)) = target {
+                sampler.push(segment, target);
+            }
+        }
+        sampler
+    }
+
+    fn push(&mut self, segment: &Segment, state: &'a UnitState) {
+        // Environmental sources are logged as unit 0
+        if state.unit_id() != 0 {
+            self.states
+                .entry((segment.session, state.unit_id()))
+                .or_default()
+                .push((segment.time, state));
+        }
+    }
+
+    // Every state of the unit in the fight's logging session, in time order
+    pub fn states(&self, fight: &Fight, unit_id: usize) -> &[(usize, &'a UnitState)] {
+        self.states
+            .get(&(fight.session, unit_id))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    // Latest (time, state) of the unit at or before the time
+    pub fn state_at(
+        &self,
+        fight: &Fight,
+        unit_id: usize,
+        time: usize,
+    ) -> Option<(usize, &'a UnitState)> {
+        let states = self.states(fight, unit_id);
+        let index = states.partition_point(|(t, _)| *t <= time);
+        index.checked_sub(1).map(|i| states[i])
+    }
+
+    pub fn series(&self, fight: &Fight, unit_id: usize, resource: Resource) -> Vec<Sample> {
+        self.states(fight, unit_id)
+            .iter()
+            .filter(|(time, _)| (fight.start_time..=fight.end_time).contains(time))
+            .map(|&(time, state)| {
+                let (current, max) = resource.of(state);
+                Sample { time, current, max }
+            })
+            .collect()
+    }
+
+    // (time, health %) through the fight, the boss health curve every report goes by
+    pub fn health_percent(&self, fight: &Fight, unit_id: usize) -> Vec<(usize, f32)> {
+        self.series(fight, unit_id, Resource::Health)
+            .iter()
+            .filter(|s| s.max > 0)
+            .map(|s| (s.time, s.current as f32 / s.max as f32 * 100.0))
+            .collect()
+    }
+
+    // Time spent in the fight while the predicate held, every sample lasts until the next one
+    pub fn time_where(
+        &self,
+        fight: &Fight,
+        unit_id: usize,
+        resource: Resource,
+        predicate: impl Fn(&Sample) -> bool,
+    ) -> usize {
+        let series = self.series(fight, unit_id, resource);
+        let ends = series
+            .iter()
+            .skip(1)
+            .map(|s| s.time)
+            .chain([fight.end_time]);
+        series
+            .iter()
+            .zip(ends)
+            .filter(|(sample, _)| sample.max > 0 && predicate(sample))
+            .map(|(sample, until)| until - sample.time)
+            .sum()
+    }
+
+    pub fn time_below(
+        &self,
+        fight: &Fight,
+        unit_id: usize,
+        resource: Resource,
+        fraction: f32,
+    ) -> usize {
+        self.time_where(fight, unit_id, resource, |s| {
+            (s.current as f32) < s.max as f32 * fraction
+        })
+    }
+
+    pub fn time_full(&self, fight: &Fight, unit_id: usize, resource: Resource) -> usize {
+        self.time_where(fight, unit_id, resource, |s| s.current >= s.max)
+    }
+}
+
+#[derive(Debug)]
+pub struct ResourceSummary {
+    pub unit_id: usize,
+    pub low_health_ms: usize,
+    pub low_magicka_ms: usize,
+    pub low_stamina_ms: usize,
+    pub ultimate_full_ms: usize,
+    // The werewolf meter only runs while transformed
+    pub werewolf_ms: usize,
+}
+
+#[derive(Debug)]
+pub struct ResourceReport<'a> {
+    fight: &'a Fight<'a>,
+    pub players: Vec<ResourceSummary>,
+}
+
+impl<'a> ResourceReport<'a> {
+    pub fn new(fight: &'a Fight<'a>, sampler: &ResourceSampler) -> Self {
+        let low =
+            |unit_id, resource| sampler.time_below(fight, unit_id, resource, LOW_RESOURCE_FRACTION);
+        let players = fight
+            .players()
+            .map(|p| ResourceSummary {
+                unit_id: p.unit_id,
+                low_health_ms: low(p.unit_id, Resource::Health),
+                low_magicka_ms: low(p.unit_id, Resource::Magicka),
+                low_stamina_ms: low(p.unit_id, Resource::Stamina),
+                ultimate_full_ms: sampler.time_full(fight, p.unit_id, Resource::Ultimate),
+                werewolf_ms: sampler
+                    .time_where(fight, p.unit_id, Resource::Werewolf, |s| s.current > 0),
+            })
+            .collect();
+        ResourceReport { fight, players }
+    }
+}
+
+impl Display for ResourceReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let duration = self.fight.duration_ms();
+        writeln!(
+            f,
+            "Fight {} time below {:.0}% resource",
+            self.fight.id,
+            LOW_RESOURCE_FRACTION * 100.0
+        )?;
+        writeln!(
+            f,
+            "  {:<24} {:>8} {:>8} {:>8} {:>9} {:>9}",
+            "Player", "Health", "Magicka", "Stamina", "Ult full", "Werewolf"
+        )?;
+        for player in &self.players {
+            writeln!(
+                f,
+                "  {:<24} {:>7.1}% {:>7.1}% {:>7.1}% {:>8.1}% {:>8.1}%",
+                self.fight.unit_name(player.unit_id),
+                percent(player.low_health_ms, duration),
+                percent(player.low_magicka_ms, duration),
+                percent(player.low_stamina_ms, duration),
+                percent(player.ultimate_full_ms, duration),
+                percent(player.werewolf_ms, duration)
+            )?;
+        }
+        Ok(())
+    }
+}
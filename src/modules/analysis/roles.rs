@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+
+use crate::modules::{
+    abilities::EffectChangeType, catalog::AbilityCatalog, combat::PlayerReaction, fight::Fight,
+    log::SegmentType, player::UnitState,
+};
+
+// Taking or healing this many times the group average marks a tank or healer
+pub const ROLE_SHARE_FACTOR: f32 = 1.5;
+
+// Lowest tank or healer score that is not classified as DPS
+pub const ROLE_SCORE_THRESHOLD: usize = 2;
+
+pub const TANK_TRAITS: [&str; 3] = ["Sturdy", "Reinforced", "Impenetrable"];
+pub const TANK_TRAIT_PIECES: usize = 3;
+pub const TANK_SETS: [usize; 1] = [232];
+pub const HEALER_SETS: [usize; 1] = [185];
+
+// Skills slotted almost exclusively by one role, taunts for tanks and group heals for healers
+pub const TANK_SKILLS: [&str; 10] = [
+    "Puncture",
+    "Pierce Armor",
+    "Ransack",
+    "Inner Fire",
+    "Inner Rage",
+    "Inner Beast",
+    "Runic Jolt",
+    "Runic Sunder",
+    "Runic Embrace",
+    "Frost Clench",
+];
+pub const HEALER_SKILLS: [&str; 10] = [
+    "Breath of Life",
+    "Combat Prayer",
+    "Healing Springs",
+    "Illustrious Healing",
+    "Radiating Regeneration",
+    "Enchanted Growth",
+    "Rapid Regeneration",
+    "Mutagen",
+    "Healing Ritual",
+    "Energy Orb",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Role {
+    Tank,
+    Healer,
+    Dps,
+}
+
+impl Role {
+    pub fn parse(role: &str) -> Option<Self> {
+        match role.to_lowercase().as_str() {
+            "tank" => Some(Role::Tank),
+            "healer" => Some(Role::Healer),
+            "dps" => Some(Role::Dps),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RoleSignals {
+    pub damage_taken: usize,
+    pub healing_done: usize,
+    // Taunt debuffs applied to hostile units
+    pub taunts: usize,
+    pub tank_trait_pieces: usize,
+    pub tank_set: bool,
+    pub healer_set: bool,
+    pub tank_skills: usize,
+    pub healer_skills: usize,
+}
+
+pub fn role_signals(fight: &Fight, catalog: &AbilityCatalog) -> BTreeMap<usize, RoleSignals> {
+    // Hidden players still take part, a filtered fight classifies the same as the full one
+    let mut signals: BTreeMap<usize, RoleSignals> = fight
+        .group()
+        .map(|p| (p.unit_id, RoleSignals::default()))
+        .collect();
+    for segment in fight.segments {
+        match &segment.line {
+            SegmentType::CombatEvent(event) => {
+                let Some(target) = event.target.state(&event.source).map(UnitState::unit_id) else {
+                    continue;
+                };
+                if event.action_result.is_damage() {
+                    if let Some(player) = signals.get_mut(&target) {
+                        player.damage_taken += event.hit_value;
+                    }
+                } else if event.action_result.is_heal() && signals.contains_key(&target) {
+                    if let Some(player) = signals.get_mut(&event.source.unit_id()) {
+                        player.healing_done += event.hit_value;
+                    }
+                }
+            }
+            SegmentType::EffectChanged(effect) => {
+                if effect.change_type != EffectChangeType::Gained
+                    || catalog.name(effect.ability_id) != "Taunt"
+                {
+                    continue;
+                }
+                let hostile = effect
+                    .target
+                    .state(&effect.source)
+                    .and_then(|t| fight.units.get(&t.unit_id()))
+                    .is_some_and(|u| u.reaction == PlayerReaction::Hostile);
+                if let Some(player) = signals.get_mut(&effect.source.unit_id()) {
+                    player.taunts += hostile as usize;
+                }
+            }
+            _ => (),
+        }
+    }
+
+    for (unit_id, player) in signals.iter_mut() {
+        let Some(info) = fight.player_info.get(unit_id) else {
+            continue;
+        };
+        for piece in info.equipment_info.pieces() {
+            player.tank_trait_pieces += TANK_TRAITS.contains(&piece.r#trait.as_str()) as usize;
+            player.tank_set |= TANK_SETS.contains(&piece.set_id);
+            player.healer_set |= HEALER_SETS.contains(&piece.set_id);
+        }
+        for ability_id in info
+            .primary_ability_id
+            .iter()
+            .chain(&info.backup_ability_id)
+        {
+            let name = catalog.name(*ability_id);
+            player.tank_skills += TANK_SKILLS.contains(&name) as usize;
+            player.healer_skills += HEALER_SKILLS.contains(&name) as usize;
+        }
+    }
+    signals
+}
+
+// Damage taken, healing done and taunts weigh 2, gear and skills 1 each
+pub fn infer_roles(fight: &Fight, catalog: &AbilityCatalog) -> BTreeMap<usize, Role> {
+    let signals = role_signals(fight, catalog);
+    let players = signals.len().max(1) as f32;
+    let total_taken: usize = signals.values().map(|s| s.damage_taken).sum();
+    let total_healed: usize = signals.values().map(|s| s.healing_done).sum();
+    let above_average = |value: usize, total: usize| {
+        total > 0 && value as f32 >= total as f32 / players * ROLE_SHARE_FACTOR
+    };
+    signals
+        .into_iter()
+        .map(|(unit_id, s)| {
+            let tank = 2 * above_average(s.damage_taken, total_taken) as usize
+                + 2 * (s.taunts > 0) as usize
+                + (s.tank_trait_pieces >= TANK_TRAIT_PIECES || s.tank_set) as usize
+                + (s.tank_skills > 0) as usize;
+            let healer = 2 * above_average(s.healing_done, total_healed) as usize
+                + s.healer_set as usize
+                + (s.healer_skills > 0) as usize;
+            let role = if tank.max(healer) < ROLE_SCORE_THRESHOLD {
+                Role::Dps
+            } else if tank >= healer {
+                Role::Tank
+            } else {
+                Role::Healer
+            };
+            (unit_id, role)
+        })
+        .collect()
+}
+
+// The fight with only the players classified as the given role left in per-player reports
+pub fn role_view<'a>(fight: &Fight<'a>, catalog: &AbilityCatalog, role: Role) -> Fight<'a> {
+    let roles = infer_roles(fight, catalog);
+    fight.with_players(|p| roles.get(&p.unit_id) == Some(&role))
+}
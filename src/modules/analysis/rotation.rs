@@ -0,0 +1,233 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+};
+
+use super::{casts::CastTracker, percent};
+use crate::modules::{catalog::AbilityCatalog, fight::Fight, log::SegmentType, player::PlayerInfo};
+
+// A light attack this long before a skill counts as woven into it
+pub const WEAVE_WINDOW_MS: usize = 1000;
+
+pub const ROTATION_SEPARATOR: &str = " > ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bar {
+    Front,
+    Back,
+}
+
+impl Bar {
+    // The bar an ability is slotted on, None if it is on neither or both
+    pub fn of(info: &PlayerInfo, ability_id: usize) -> Option<Bar> {
+        match (
+            info.primary_ability_id.contains(&ability_id),
+            info.backup_ability_id.contains(&ability_id),
+        ) {
+            (true, false) => Some(Bar::Front),
+            (false, true) => Some(Bar::Back),
+            _ => None,
+        }
+    }
+
+    pub fn other(self) -> Bar {
+        match self {
+            Bar::Front => Bar::Back,
+            Bar::Back => Bar::Front,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RotationEntry {
+    pub time: usize,
+    pub ability_id: usize,
+    pub bar: Option<Bar>,
+    pub light_attack: bool,
+    // Skill preceded by a light attack within the weave window
+    pub woven: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct PlayerRotation {
+    pub entries: Vec<RotationEntry>,
+}
+
+impl PlayerRotation {
+    pub fn skills(&self) -> impl Iterator<Item = &RotationEntry> {
+        self.entries.iter().filter(|e| !e.light_attack)
+    }
+
+    pub fn light_attacks(&self) -> usize {
+        self.entries.iter().filter(|e| e.light_attack).count()
+    }
+
+    pub fn woven(&self) -> usize {
+        self.skills().filter(|e| e.woven).count()
+    }
+
+    pub fn casts_per_minute(&self, duration_ms: usize) -> f32 {
+        self.skills().count() as f32 / (duration_ms.max(1) as f32 / 60_000.0)
+    }
+
+    pub fn rotation_string(&self, catalog: &AbilityCatalog) -> String {
+        self.entries
+            .iter()
+            .map(|e| catalog.name(e.ability_id))
+            .collect::<Vec<_>>()
+            .join(ROTATION_SEPARATOR)
+    }
+
+    // Share of the reference rotation performed in order, by longest common subsequence
+    pub fn matches_reference(&self, catalog: &AbilityCatalog, reference: &str) -> f32 {
+        let reference: Vec<&str> = reference.split(ROTATION_SEPARATOR).map(str::trim).collect();
+        let mut lengths = vec![0; reference.len() + 1];
+        for entry in &self.entries {
+            let name = catalog.name(entry.ability_id);
+            let mut diagonal = 0;
+            for (i, step) in reference.iter().enumerate() {
+                let above = lengths[i + 1];
+                lengths[i + 1] = if *step == name {
+                    diagonal + 1
+                } else {
+                    above.max(lengths[i])
+                };
+                diagonal = above;
+            }
+        }
+        lengths[reference.len()] as f32 / reference.len() as f32 * 100.0
+    }
+}
+
+#[derive(Debug)]
+pub struct RotationReport<'a> {
+    fight: &'a Fight<'a>,
+    catalog: &'a AbilityCatalog<'a>,
+    reference: Option<&'a str>,
+    pub players: BTreeMap<usize, PlayerRotation>,
+}
+
+impl<'a> RotationReport<'a> {
+    pub fn new(
+        fight: &'a Fight<'a>,
+        tracker: &CastTracker,
+        catalog: &'a AbilityCatalog<'a>,
+        reference: Option<&'a str>,
+    ) -> Self {
+        let is_light_attack = |ability_id| catalog.name(ability_id).starts_with("Light Attack");
+        let mut players: BTreeMap<usize, PlayerRotation> = fight
+            .players()
+            .map(|p| (p.unit_id, PlayerRotation::default()))
+            .collect();
+
+        let mut tracked = HashSet::new();
+        for cast in tracker.in_fight(fight) {
+            let Some(player) = players.get_mut(&cast.source) else {
+                continue;
+            };
+            tracked.insert(cast.cast_track_id);
+            player.entries.push(RotationEntry {
+                time: cast.begin_time,
+                ability_id: cast.ability_id,
+                bar: None,
+                light_attack: is_light_attack(cast.ability_id),
+                woven: false,
+            });
+        }
+        // Light attacks are not always logged as casts, only by their damage
+        for segment in fight.segments {
+            let SegmentType::CombatEvent(event) = &segment.line else {
+                continue;
+            };
+            if !event.action_result.is_damage()
+                || !is_light_attack(event.ability_id)
+                || tracked.contains(&event.cast_track_id)
+            {
+                continue;
+            }
+            if let Some(player) = players.get_mut(&event.source.unit_id()) {
+                if event.cast_track_id != 0 {
+                    tracked.insert(event.cast_track_id);
+                }
+                player.entries.push(RotationEntry {
+                    time: segment.time,
+                    ability_id: event.ability_id,
+                    bar: None,
+                    light_attack: true,
+                    woven: false,
+                });
+            }
+        }
+
+        for (unit_id, player) in players.iter_mut() {
+            player.entries.sort_by_key(|e| e.time);
+            let info = fight.player_info.get(unit_id);
+            // Abilities slotted on both bars stay on whichever bar was last seen
+            let mut bar = None;
+            let mut last_light_attack = None;
+            for entry in player.entries.iter_mut() {
+                if entry.light_attack {
+                    last_light_attack = Some(entry.time);
+                    continue;
+                }
+                if let Some(slotted) = info.and_then(|i| Bar::of(i, entry.ability_id)) {
+                    bar = Some(slotted);
+                }
+                entry.bar = bar;
+                entry.woven = last_light_attack
+                    .take()
+                    .is_some_and(|t| entry.time - t <= WEAVE_WINDOW_MS);
+            }
+        }
+
+        RotationReport {
+            fight,
+            catalog,
+            reference,
+            players,
+        }
+    }
+}
+
+impl Display for RotationReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Fight {} rotations", self.fight.id)?;
+        for (unit_id, player) in &self.players {
+            let skills = player.skills().count();
+            write!(
+                f,
+                "  {:<24} {:>5.1} casts/min {:>4} light attacks {:>5.1}% woven",
+                self.fight.unit_name(*unit_id),
+                player.casts_per_minute(self.fight.duration_ms()),
+                player.light_attacks(),
+                percent(player.woven(), skills)
+            )?;
+            match self.reference {
+                Some(reference) => writeln!(
+                    f,
+                    " {:>5.1}% of reference",
+                    player.matches_reference(self.catalog, reference)
+                )?,
+                None => writeln!(f)?,
+            }
+            for entry in &player.entries {
+                let bar = match (entry.light_attack, entry.bar) {
+                    (true, _) => "LA",
+                    (_, Some(Bar::Front)) => "F",
+                    (_, Some(Bar::Back)) => "B",
+                    (_, None) => "?",
+                };
+                writeln!(
+                    f,
+                    "    {:>7.1}s [{:<2}] {}{}",
+                    (entry.time - self.fight.start_time) as f32 / 1000.0,
+                    bar,
+                    self.catalog.name(entry.ability_id),
+                    if entry.woven { " (woven)" } else { "" }
+                )?;
+            }
+            writeln!(f, "    {}", player.rotation_string(self.catalog))?;
+        }
+        Ok(())
+    }
+}
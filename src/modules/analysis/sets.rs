@@ -0,0 +1,411 @@
+// Set ids as logged in PLAYER_INFO, sorted by id. Anything missing is shown by its id
+pub const SET_NAMES: [(usize, &str); 408] = [
+    (19, "Vestments of the Warlock"),
+    (20, "Witchman Armor"),
+    (21, "Akaviri Dragonguard"),
+    (22, "Dreamer's Mantle"),
+    (23, "Archer's Mind"),
+    (24, "Footman's Fortune"),
+    (25, "Desert Rose"),
+    (26, "Prisoner's Rags"),
+    (27, "Fiord's Legacy"),
+    (28, "Barkskin"),
+    (29, "Sergeant's Mail"),
+    (30, "Thunderbug's Carapace"),
+    (31, "Silks of the Sun"),
+    (32, "Healer's Habit"),
+    (33, "Viper's Sting"),
+    (34, "Night Mother's Embrace"),
+    (35, "Knightmare"),
+    (36, "Armor of the Veiled Heritance"),
+    (37, "Death's Wind"),
+    (38, "Twilight's Embrace"),
+    (39, "Alessian Order"),
+    (40, "Night's Silence"),
+    (41, "Whitestrake's Retribution"),
+    (43, "Armor of the Seducer"),
+    (44, "Vampire's Kiss"),
+    (46, "Noble Duelist's Silks"),
+    (47, "Robes of the Withered Hand"),
+    (48, "Magnus' Gift"),
+    (49, "Shadow of the Red Mountain"),
+    (50, "The Morag Tong"),
+    (51, "Night Mother's Gaze"),
+    (52, "Beckoning Steel"),
+    (53, "The Ice Furnace"),
+    (54, "Ashen Grip"),
+    (55, "Prayer Shawl"),
+    (56, "Stendarr's Embrace"),
+    (57, "Syrabane's Grip"),
+    (58, "Hide of the Werewolf"),
+    (59, "Kyne's Kiss"),
+    (60, "Darkstride"),
+    (61, "Dreugh King Slayer"),
+    (62, "Hatchling's Shell"),
+    (63, "The Juggernaut"),
+    (64, "Shadow Dancer's Raiment"),
+    (65, "Bloodthorn's Touch"),
+    (66, "Robes of the Hist"),
+    (67, "Shadow Walker"),
+    (68, "Stygian"),
+    (69, "Ranger's Gait"),
+    (70, "Seventh Legion Brute"),
+    (71, "Durok's Bane"),
+    (72, "Nikulas' Heavy Armor"),
+    (73, "Oblivion's Foe"),
+    (74, "Spectre's Eye"),
+    (75, "Torug's Pact"),
+    (76, "Robes of Alteration Mastery"),
+    (77, "Crusader"),
+    (78, "Hist Bark"),
+    (79, "Willow's Path"),
+    (80, "Hunding's Rage"),
+    (81, "Song of Lamae"),
+    (82, "Alessia's Bulwark"),
+    (83, "Elf Bane"),
+    (84, "Orgnum's Scales"),
+    (85, "Almalexia's Mercy"),
+    (86, "Queen's Elegance"),
+    (87, "Eyes of Mara"),
+    (88, "Robes of Destruction Mastery"),
+    (89, "Sentry"),
+    (90, "Senche's Bite"),
+    (91, "Oblivion's Edge"),
+    (92, "Kagrenac's Hope"),
+    (93, "Storm Knight's Plate"),
+    (94, "Meridia's Blessed Armor"),
+    (95, "Shalidor's Curse"),
+    (96, "Armor of Truth"),
+    (97, "The Arch-Mage"),
+    (98, "Necropotence"),
+    (99, "Salvation"),
+    (100, "Hawk's Eye"),
+    (101, "Affliction"),
+    (102, "Duneripper's Scales"),
+    (103, "Magicka Furnace"),
+    (104, "Curse Eater"),
+    (105, "Twin Sisters"),
+    (106, "Wilderqueen's Arch"),
+    (107, "Wyrd Tree's Blessing"),
+    (108, "Ravager"),
+    (109, "Light of Cyrodiil"),
+    (110, "Sanctuary"),
+    (111, "Ward of Cyrodiil"),
+    (112, "Night Terror"),
+    (113, "Crest of Cyrodiil"),
+    (114, "Soulshine"),
+    (116, "The Destruction Suite"),
+    (117, "Relics of the Physician, Ansur"),
+    (118, "Treasures of the Earthforge"),
+    (119, "Relics of the Rebellion"),
+    (120, "Arms of Infernace"),
+    (121, "Arms of the Ancestors"),
+    (122, "Ebon Armory"),
+    (123, "Hircine's Veneer"),
+    (124, "The Worm's Raiment"),
+    (125, "Wrath of the Imperium"),
+    (126, "Grace of the Ancients"),
+    (127, "Deadly Strike"),
+    (128, "Blessing of the Potentates"),
+    (129, "Vengeance Leech"),
+    (130, "Eagle Eye"),
+    (131, "Bastion of the Heartland"),
+    (132, "Shield of the Valiant"),
+    (133, "Buffer of the Swift"),
+    (134, "Shroud of the Lich"),
+    (135, "Draugr's Heritage"),
+    (136, "Immortal Warrior"),
+    (137, "Berserking Warrior"),
+    (138, "Defending Warrior"),
+    (139, "Wise Mage"),
+    (140, "Destructive Mage"),
+    (141, "Healing Mage"),
+    (142, "Quick Serpent"),
+    (143, "Poisonous Serpent"),
+    (144, "Twice-Fanged Serpent"),
+    (145, "Way of Fire"),
+    (146, "Way of Air"),
+    (147, "Way of Martial Knowledge"),
+    (148, "Way of the Arena"),
+    (155, "Undaunted Bastion"),
+    (156, "Undaunted Infiltrator"),
+    (157, "Undaunted Unweaver"),
+    (158, "Embershield"),
+    (159, "Sunderflame"),
+    (160, "Burning Spellweave"),
+    (161, "Twice-Born Star"),
+    (162, "Spawn of Mephala"),
+    (163, "Blood Spawn"),
+    (164, "Lord Warden"),
+    (165, "Scourge Harvester"),
+    (166, "Engine Guardian"),
+    (167, "Nightflame"),
+    (168, "Nerien'eth"),
+    (169, "Valkyn Skoria"),
+    (170, "Maw of the Infernal"),
+    (171, "Eternal Warrior"),
+    (172, "Infallible Mage"),
+    (173, "Vicious Death"),
+    (176, "Noble's Conquest"),
+    (177, "Redistributor"),
+    (178, "Armor Master"),
+    (179, "Black Rose"),
+    (180, "Powerful Assault"),
+    (181, "Meritorious Service"),
+    (183, "Molag Kena"),
+    (184, "Brands of Imperium"),
+    (185, "Spell Power Cure"),
+    (186, "Jolting Arms"),
+    (187, "Swamp Raider"),
+    (188, "Storm Master"),
+    (190, "Scathing Mage"),
+    (193, "Overwhelming Surge"),
+    (194, "Combat Physician"),
+    (195, "Sheer Venom"),
+    (196, "Leeching Plate"),
+    (197, "Tormentor"),
+    (198, "Essence Thief"),
+    (199, "Shield Breaker"),
+    (200, "Phoenix"),
+    (201, "Reactive Armor"),
+    (204, "Endurance"),
+    (205, "Willpower"),
+    (206, "Agility"),
+    (207, "Law of Julianos"),
+    (208, "Trial by Fire"),
+    (209, "Armor of the Code"),
+    (210, "Mark of the Pariah"),
+    (211, "Permafrost"),
+    (212, "Briarheart"),
+    (213, "Glorious Defender"),
+    (214, "Para Bellum"),
+    (215, "Elemental Succession"),
+    (216, "Hunt Leader"),
+    (217, "Winterborn"),
+    (218, "Trinimac's Valor"),
+    (219, "Morkuldin"),
+    (224, "Tava's Favor"),
+    (225, "Clever Alchemist"),
+    (226, "Eternal Hunt"),
+    (227, "Bahraha's Curse"),
+    (228, "Syvarra's Scales"),
+    (229, "Twilight Remedy"),
+    (230, "Moondancer"),
+    (231, "Lunar Bastion"),
+    (232, "Roar of Alkosh"),
+    (234, "Marksman's Crest"),
+    (235, "Robes of Transmutation"),
+    (236, "Vicious Serpent"),
+    (237, "Leki's Focus"),
+    (238, "Fasalla's Guile"),
+    (239, "Warrior's Fury"),
+    (240, "Kvatch Gladiator"),
+    (241, "Varen's Legacy"),
+    (242, "Pelinal's Aptitude"),
+    (243, "Hide of Morihaus"),
+    (244, "Flanking Strategist"),
+    (245, "Sithis' Touch"),
+    (246, "Galerion's Revenge"),
+    (247, "Vicecanon of Venom"),
+    (253, "Imperial Physique"),
+    (256, "Mighty Chudan"),
+    (257, "Velidreth"),
+    (258, "Amber Plasm"),
+    (259, "Heem-Jas' Retribution"),
+    (260, "Aspect of Mazzatun"),
+    (261, "Gossamer"),
+    (262, "Widowmaker"),
+    (263, "Hand of Mephala"),
+    (264, "Giant Spider"),
+    (265, "Shadowrend"),
+    (266, "Kra'gh"),
+    (267, "Swarm Mother"),
+    (268, "Sentinel of Rkugamz"),
+    (269, "Chokethorn"),
+    (270, "Slimecraw"),
+    (271, "Sellistrix"),
+    (272, "Infernal Guardian"),
+    (273, "Ilambris"),
+    (274, "Iceheart"),
+    (275, "Stormfist"),
+    (276, "Tremorscale"),
+    (277, "Pirate Skeleton"),
+    (278, "The Troll King"),
+    (279, "Selene"),
+    (280, "Grothdarr"),
+    (281, "Armor of the Trainee"),
+    (282, "Vampire Cloak"),
+    (283, "Sword-Singer"),
+    (284, "Order of Diagna"),
+    (285, "Vampire Lord"),
+    (286, "Spriggan's Thorns"),
+    (287, "Green Pact"),
+    (288, "Beekeeper's Gear"),
+    (289, "Spinner's Garments"),
+    (290, "Skooma Smuggler"),
+    (291, "Shalk Exoskeleton"),
+    (292, "Mother's Sorrow"),
+    (293, "Plague Doctor"),
+    (294, "Ysgramor's Birthright"),
+    (295, "Jailbreaker"),
+    (296, "Spelunker"),
+    (297, "Spider Cultist Cowl"),
+    (298, "Light Speaker"),
+    (299, "Toothrow"),
+    (300, "Netch's Touch"),
+    (301, "Strength of the Automaton"),
+    (302, "Leviathan"),
+    (303, "Lamia's Song"),
+    (304, "Medusa"),
+    (305, "Treasure Hunter"),
+    (307, "Draugr Hulk"),
+    (308, "Bone Pirate's Tatters"),
+    (309, "Knight-errant's Mail"),
+    (310, "Sword Dancer"),
+    (311, "Rattlecage"),
+    (313, "Titanic Cleave"),
+    (314, "Puncturing Remedy"),
+    (315, "Stinging Slashes"),
+    (316, "Caustic Arrow"),
+    (317, "Destructive Impact"),
+    (318, "Grand Rejuvenation"),
+    (320, "War Maiden"),
+    (321, "Defiler"),
+    (322, "Warrior-Poet"),
+    (323, "Assassin's Guile"),
+    (324, "Daedric Trickery"),
+    (325, "Shacklebreaker"),
+    (326, "Vanguard's Challenge"),
+    (327, "Coward's Gear"),
+    (328, "Knight Slayer"),
+    (329, "Wizard's Riposte"),
+    (330, "Automated Defense"),
+    (331, "War Machine"),
+    (332, "Master Architect"),
+    (333, "Inventor's Guard"),
+    (334, "Impregnable Armor"),
+    (335, "Draugr's Rest"),
+    (336, "Pillar of Nirn"),
+    (337, "Ironblood"),
+    (338, "Flame Blossom"),
+    (339, "Blooddrinker"),
+    (340, "Hagraven's Garden"),
+    (341, "Earthgore"),
+    (342, "Domihaus"),
+    (343, "Caluurion's Legacy"),
+    (344, "Trappings of Invigoration"),
+    (345, "Ulfnor's Favor"),
+    (346, "Jorvuld's Guidance"),
+    (347, "Plague Slinger"),
+    (348, "Curse of Doylemish"),
+    (349, "Thurvokun"),
+    (350, "Zaan"),
+    (351, "Innate Axiom"),
+    (352, "Fortified Brass"),
+    (353, "Mechanical Acuity"),
+    (354, "Mad Tinkerer"),
+    (355, "Unfathomable Darkness"),
+    (356, "Livewire"),
+    (380, "Prophet's"),
+    (381, "Broken Soul"),
+    (382, "Grace of Gloom"),
+    (383, "Gryphon's Ferocity"),
+    (384, "Wisdom of Vanus"),
+    (385, "Adept Rider"),
+    (386, "Sload's Semblance"),
+    (387, "Nocturnal's Favor"),
+    (388, "Aegis of Galenwe"),
+    (389, "Arms of Relequen"),
+    (390, "Mantle of Siroria"),
+    (391, "Vestment of Olorime"),
+    (392, "Perfected Aegis of Galenwe"),
+    (393, "Perfected Arms of Relequen"),
+    (394, "Perfected Mantle of Siroria"),
+    (395, "Perfected Vestment of Olorime"),
+    (397, "Balorgh"),
+    (398, "Vykosa"),
+    (399, "Hanu's Compassion"),
+    (400, "Blood Moon"),
+    (401, "Haven of Ursus"),
+    (402, "Moon Hunter"),
+    (403, "Savage Werewolf"),
+    (404, "Jailer's Tenacity"),
+    (405, "Bright-Throat's Boast"),
+    (406, "Dead-Water's Guile"),
+    (407, "Champion of the Hist"),
+    (408, "Grave-Stake Collector"),
+    (409, "Naga Shaman"),
+    (410, "Might of the Lost Legion"),
+    (411, "Gallant Charge"),
+    (412, "Radial Uppercut"),
+    (413, "Spectral Cloak"),
+    (414, "Virulent Shot"),
+    (415, "Wild Impulse"),
+    (416, "Mender's Ward"),
+    (417, "Indomitable Fury"),
+    (418, "Spell Strategist"),
+    (419, "Battlefield Acrobat"),
+    (420, "Soldier of Anguish"),
+    (421, "Steadfast Hero"),
+    (422, "Battalion Defender"),
+    (429, "Mighty Glacier"),
+    (430, "Tzogvin's Warband"),
+    (431, "Icy Conjuror"),
+    (432, "Stonekeeper"),
+    (433, "Frozen Watcher"),
+    (434, "Scavenging Demise"),
+    (435, "Auroran's Thunder"),
+    (436, "Symphony of Blades"),
+    (437, "Coldharbour's Favorite"),
+    (438, "Senche-raht's Grit"),
+    (439, "Vastarie's Tutelage"),
+    (440, "Crafty Alfiq"),
+    (441, "Vesture of Darloc Brae"),
+    (442, "Call of the Undertaker"),
+    (443, "Eye of Nahviintaas"),
+    (444, "False God's Devotion"),
+    (445, "Tooth of Lokkestiiz"),
+    (446, "Claw of Yolnahkriin"),
+    (448, "Perfected Eye of Nahviintaas"),
+    (449, "Perfected False God's Devotion"),
+    (450, "Perfected Tooth of Lokkestiiz"),
+    (451, "Perfected Claw of Yolnahkriin"),
+    (452, "Hollowfang Thirst"),
+    (453, "Dro'Zakar's Claws"),
+    (454, "Renald's Resolve"),
+    (455, "Z'en's Redress"),
+    (456, "Azureblight Reaper"),
+    (457, "Dragon's Defilement"),
+    (458, "Grundwulf"),
+    (459, "Maarselok"),
+    (465, "Senchal Defender"),
+    (466, "Marauder's Haste"),
+    (467, "Dragonguard Elite"),
+    (468, "Daring Corsair"),
+    (469, "Ancient Dragonguard"),
+    (470, "New Moon Acolyte"),
+    (471, "Hiti's Hearth"),
+    (472, "Titanborn Strength"),
+    (473, "Bani's Torment"),
+    (474, "Draugrkin's Grip"),
+    (475, "Aegis Caller"),
+    (476, "Grave Guardian"),
+    (478, "Mother Ciannait"),
+    (479, "Kjalnar's Nightmare"),
+    (480, "Critical Riposte"),
+    (481, "Unchained Aggressor"),
+    (482, "Dauntless Combatant"),
+    (487, "Winter's Respite"),
+    (488, "Venomous Smite"),
+    (489, "Eternal Vigor"),
+    (490, "Stuhn's Favor"),
+    (491, "Dragon's Appetite"),
+    (492, "Kyne's Wind"),
+    (493, "Perfected Kyne's Wind"),
+    (494, "Vrol's Command"),
+    (495, "Perfected Vrol's Command"),
+    (496, "Roaring Opportunist"),
+    (497, "Perfected Roaring Opportunist"),
+    (498, "Yandir's Might"),
+    (499, "Perfected Yandir's Might"),
+];
@@ -0,0 +1,216 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+};
+
+use super::{
+    percent,
+    resources::{Resource, ResourceSampler},
+};
+use crate::modules::{
+    abilities::{ActionResult, EffectChangeType},
+    catalog::AbilityCatalog,
+    fight::Fight,
+    log::{Segment, SegmentType},
+    player::{Targets, UnitState},
+};
+
+// Damage soaked by a damage shield
+#[derive(Debug)]
+pub struct Absorb {
+    // Logging session, see Segment::session
+    pub session: usize,
+    pub time: usize,
+    pub target: usize,
+    pub amount: usize,
+    // (caster, ability) of the shield, when one could be inferred
+    pub shield: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Default)]
+pub struct ShieldTracker {
+    pub absorbs: Vec<Absorb>,
+}
+
+impl ShieldTracker {
+    // An effect is taken to be a shield when gaining it raised the target's shield value,
+    // absorbs are credited to the most recently applied shield still on the target
+    pub fn new(segments: &[Segment]) -> Self {
+        let mut last_shield: HashMap<usize, usize> = HashMap::new();
+        let mut active: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+        let mut absorbs = vec![];
+        for segment in segments {
+            match &segment.line {
+                SegmentType::EffectChanged(effect) => {
+                    let Some(target) = effect.target.state(&effect.source) else {
+                        continue;
+                    };
+                    let shields = active.entry(target.unit_id()).or_default();
+                    let applied = (effect.source.unit_id(), effect.ability_id);
+                    match effect.change_type {
+                        EffectChangeType::Faded => shields.retain(|s| *s != applied),
+                        _ => {
+                            let previous = last_shield.get(&target.unit_id()).copied();
+                            if target.shield() > previous.unwrap_or_default() {
+                                shields.retain(|s| *s != applied);
+                                shields.push(applied);
+                            }
+                        }
+                    }
+                    observe(&mut last_shield, &effect.source, &effect.target);
+                }
+                SegmentType::CombatEvent(event) => {
+                    if matches!(
+                        event.action_result,
+                        ActionResult::DamageShielded | ActionResult::Absorbed
+                    ) {
+                        if let Some(target) = event.target.state(&event.source) {
+                            absorbs.push(Absorb {
+                                session: segment.session,
+                                time: segment.time,
+                                target: target.unit_id(),
+                                amount: event.hit_value,
+                                shield: active
+                                    .get(&target.unit_id())
+                                    .and_then(|s| s.last())
+                                    .copied(),
+                            });
+                        }
+                    }
+                    observe(&mut last_shield, &event.source, &event.target);
+                }
+                SegmentType::BeginCast(cast) => {
+                    observe(&mut last_shield, &cast.source, &cast.target)
+                }
+                SegmentType::UnitRemoved(unit) => {
+                    active.remove(&unit.unit_id);
+                    last_shield.remove(&unit.unit_id);
+                }
+                // Unit ids restart with every session
+                SegmentType::BeginLog(_) => {
+                    active.clear();
+                    last_shield.clear();
+                }
+                _ => (),
+            }
+        }
+        ShieldTracker { absorbs }
+    }
+}
+
+fn observe(last_shield: &mut HashMap<usize, usize>, source: &UnitState, target: &Targets) {
+    last_shield.insert(source.unit_id(), source.shield());
+    if let Targets::Target(target) = target {
+        last_shield.insert(target.unit_id(), target.shield());
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct UnitShield {
+    pub absorbed: usize,
+    pub peak: usize,
+    pub shielded_ms: usize,
+}
+
+#[derive(Debug)]
+pub struct ShieldReport<'a> {
+    fight: &'a Fight<'a>,
+    catalog: &'a AbilityCatalog<'a>,
+    pub units: BTreeMap<usize, UnitShield>,
+    // (caster, ability) -> damage mitigated, unattributed absorbs are keyed (0, 0)
+    pub mitigated: BTreeMap<(usize, usize), usize>,
+}
+
+impl<'a> ShieldReport<'a> {
+    pub fn new(
+        fight: &'a Fight<'a>,
+        tracker: &ShieldTracker,
+        sampler: &ResourceSampler,
+        catalog: &'a AbilityCatalog<'a>,
+    ) -> Self {
+        let (session, start, end) = (fight.session, fight.start_time, fight.end_time);
+        let mut units: BTreeMap<usize, UnitShield> = fight
+            .players()
+            .map(|p| {
+                let series = sampler.series(fight, p.unit_id, Resource::Shield);
+                let shield = UnitShield {
+                    absorbed: 0,
+                    peak: series.iter().map(|s| s.current).max().unwrap_or_default(),
+                    shielded_ms: sampler
+                        .time_where(fight, p.unit_id, Resource::Shield, |s| s.current > 0),
+                };
+                (p.unit_id, shield)
+            })
+            .collect();
+        let mut mitigated = BTreeMap::new();
+        for absorb in tracker
+            .absorbs
+            .iter()
+            .filter(|a| a.session == session && (start..=end).contains(&a.time))
+        {
+            if let Some(unit) = units.get_mut(&absorb.target) {
+                unit.absorbed += absorb.amount;
+            }
+            *mitigated
+                .entry(absorb.shield.unwrap_or_default())
+                .or_default() += absorb.amount;
+        }
+        ShieldReport {
+            fight,
+            catalog,
+            units,
+            mitigated,
+        }
+    }
+
+    // Total damage mitigated by the shields of each caster
+    pub fn by_caster(&self) -> BTreeMap<usize, usize> {
+        let mut casters = BTreeMap::new();
+        for ((caster, _), amount) in &self.mitigated {
+            *casters.entry(*caster).or_default() += amount;
+        }
+        casters
+    }
+}
+
+impl Display for ShieldReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Fight {} damage shields", self.fight.id)?;
+        writeln!(
+            f,
+            "  {:<24} {:>10} {:>8} {:>9}",
+            "Player", "Absorbed", "Peak", "Shielded"
+        )?;
+        for (unit_id, unit) in &self.units {
+            writeln!(
+                f,
+                "  {:<24} {:>10} {:>8} {:>8.1}%",
+                self.fight.unit_name(*unit_id),
+                unit.absorbed,
+                unit.peak,
+                percent(unit.shielded_ms, self.fight.duration_ms())
+            )?;
+        }
+        writeln!(f, "  Mitigated by caster")?;
+        for (caster, amount) in self.by_caster() {
+            let name = match caster {
+                0 => "Unattributed",
+                caster => self.fight.unit_name(caster),
+            };
+            writeln!(f, "    {:<24} {:>10}", name, amount)?;
+            for ((_, ability_id), amount) in
+                self.mitigated.range((caster, 0)..=(caster, usize::MAX))
+            {
+                if caster != 0 {
+                    writeln!(
+                        f,
+                        "      {:<22} {:>10}",
+                        self.catalog.name(*ability_id),
+                        amount
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
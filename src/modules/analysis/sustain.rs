@@ -0,0 +1,138 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::modules::{
+    abilities::{ActionResult, PowerType},
+    catalog::AbilityCatalog,
+    fight::Fight,
+    log::SegmentType,
+};
+
+pub const SUSTAIN_POWER_TYPES: [PowerType; 5] = [
+    PowerType::Magicka,
+    PowerType::Stamina,
+    PowerType::Health,
+    PowerType::Ultimate,
+    PowerType::Werewolf,
+];
+
+#[derive(Debug, Default)]
+pub struct SustainSource {
+    pub restored: usize,
+    pub drained: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct PlayerSustain {
+    pub sources: BTreeMap<(usize, PowerType), SustainSource>,
+    // (time, ability id) of every cast that failed for lack of resources
+    pub insufficient: Vec<(usize, usize)>,
+}
+
+impl PlayerSustain {
+    pub fn total(&self, power_type: PowerType) -> SustainSource {
+        self.sources
+            .iter()
+            .filter(|((_, p), _)| *p == power_type)
+            .fold(SustainSource::default(), |mut total, (_, s)| {
+                total.restored += s.restored;
+                total.drained += s.drained;
+                total
+            })
+    }
+}
+
+#[derive(Debug)]
+pub struct SustainReport<'a> {
+    fight: &'a Fight<'a>,
+    catalog: &'a AbilityCatalog<'a>,
+    pub players: BTreeMap<usize, PlayerSustain>,
+}
+
+impl<'a> SustainReport<'a> {
+    pub fn new(fight: &'a Fight<'a>, catalog: &'a AbilityCatalog<'a>) -> Self {
+        let mut players: BTreeMap<usize, PlayerSustain> = fight
+            .players()
+            .map(|p| (p.unit_id, PlayerSustain::default()))
+            .collect();
+        for segment in fight.segments {
+            let SegmentType::CombatEvent(event) = &segment.line else {
+                continue;
+            };
+            if event.action_result == ActionResult::InsufficientResource {
+                if let Some(player) = players.get_mut(&event.source.unit_id()) {
+                    player.insufficient.push((segment.time, event.ability_id));
+                }
+                continue;
+            }
+            if !SUSTAIN_POWER_TYPES.contains(&event.power_type) {
+                continue;
+            }
+            let Some(player) = event
+                .target
+                .state(&event.source)
+                .and_then(|t| players.get_mut(&t.unit_id()))
+            else {
+                continue;
+            };
+            let source = player
+                .sources
+                .entry((event.ability_id, event.power_type))
+                .or_default();
+            match event.action_result {
+                ActionResult::PowerEnergize => source.restored += event.hit_value,
+                ActionResult::PowerDrain => source.drained += event.hit_value,
+                _ => (),
+            }
+        }
+        SustainReport {
+            fight,
+            catalog,
+            players,
+        }
+    }
+}
+
+impl Display for SustainReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let seconds = self.fight.duration_ms().max(1) as f32 / 1000.0;
+        writeln!(f, "Fight {} sustain", self.fight.id)?;
+        for (unit_id, player) in &self.players {
+            writeln!(f, "  {}", self.fight.unit_name(*unit_id))?;
+            for power_type in SUSTAIN_POWER_TYPES {
+                let total = player.total(power_type);
+                if total.restored + total.drained == 0 {
+                    continue;
+                }
+                writeln!(
+                    f,
+                    "    {:<10} +{:.1}/s -{:.1}/s",
+                    format!("{power_type:?}"),
+                    total.restored as f32 / seconds,
+                    total.drained as f32 / seconds
+                )?;
+            }
+            for ((ability_id, power_type), source) in &player.sources {
+                if source.restored + source.drained == 0 {
+                    continue;
+                }
+                writeln!(
+                    f,
+                    "      {:<32} {:<10} +{:<8} -{}",
+                    self.catalog.name(*ability_id),
+                    format!("{power_type:?}"),
+                    source.restored,
+                    source.drained
+                )?;
+            }
+            for (time, ability_id) in &player.insufficient {
+                writeln!(
+                    f,
+                    "      {:>7.1}s insufficient resource for {}",
+                    (time - self.fight.start_time) as f32 / 1000.0,
+                    self.catalog.name(*ability_id)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
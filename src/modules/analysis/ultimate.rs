@@ -0,0 +1,181 @@
+use std::fmt::Display;
+
+use super::{
+    casts::CastTracker,
+    percent,
+    resources::{Resource, ResourceSampler},
+};
+use crate::modules::{
+    abilities::{ActionResult, PowerType},
+    catalog::AbilityCatalog,
+    fight::Fight,
+    log::SegmentType,
+    player::UnitState,
+};
+
+// Ultimates whose buffs or debuffs don't stack, (family, morph names, effect duration)
+pub const GROUP_ULTIMATES: [(&str, &[&str], usize); 3] = [
+    (
+        "Warhorn",
+        &["War Horn", "Aggressive Horn", "Sturdy Horn"],
+        10_000,
+    ),
+    (
+        "Colossus",
+        &["Frozen Colossus", "Glacial Colossus", "Pestilent Colossus"],
+        12_000,
+    ),
+    (
+        "Barrier",
+        &["Barrier", "Replenishing Barrier", "Reviving Barrier"],
+        30_000,
+    ),
+];
+
+#[derive(Debug)]
+pub struct UltimateUsage {
+    pub unit_id: usize,
+    pub gained: usize,
+    pub spent: usize,
+    // Slotted front and back bar ultimates
+    pub slotted: [usize; 2],
+    pub casts: usize,
+    pub capped_ms: usize,
+}
+
+// A group ultimate cast while another of the same family was still active
+#[derive(Debug)]
+pub struct UltimateOverlap {
+    pub family: &'static str,
+    pub time: usize,
+    pub first: (usize, usize),
+    pub second: (usize, usize),
+}
+
+#[derive(Debug)]
+pub struct UltimateReport<'a> {
+    fight: &'a Fight<'a>,
+    catalog: &'a AbilityCatalog<'a>,
+    pub players: Vec<UltimateUsage>,
+    pub overlaps: Vec<UltimateOverlap>,
+}
+
+impl<'a> UltimateReport<'a> {
+    pub fn new(
+        fight: &'a Fight<'a>,
+        tracker: &CastTracker,
+        sampler: &ResourceSampler,
+        catalog: &'a AbilityCatalog<'a>,
+    ) -> Self {
+        let mut players = vec![];
+        for player in fight.players() {
+            let slotted = fight
+                .player_info
+                .get(&player.unit_id)
+                .map(|info| [info.primary_ability_id[5], info.backup_ability_id[5]])
+                .unwrap_or_default();
+            let mut usage = UltimateUsage {
+                unit_id: player.unit_id,
+                gained: 0,
+                spent: 0,
+                slotted,
+                casts: tracker
+                    .in_fight(fight)
+                    .filter(|c| {
+                        c.source == player.unit_id
+                            && c.ability_id != 0
+                            && slotted.contains(&c.ability_id)
+                    })
+                    .count(),
+                capped_ms: sampler.time_full(fight, player.unit_id, Resource::Ultimate),
+            };
+            for segment in fight.segments {
+                let SegmentType::CombatEvent(event) = &segment.line else {
+                    continue;
+                };
+                if event.power_type != PowerType::Ultimate
+                    || event.target.state(&event.source).map(UnitState::unit_id)
+                        != Some(player.unit_id)
+                {
+                    continue;
+                }
+                match event.action_result {
+                    ActionResult::PowerEnergize => usage.gained += event.hit_value,
+                    ActionResult::PowerDrain => usage.spent += event.hit_value,
+                    _ => (),
+                }
+            }
+            players.push(usage);
+        }
+
+        let mut overlaps = vec![];
+        for (family, names, duration) in GROUP_ULTIMATES {
+            let casts: Vec<_> = tracker
+                .in_fight(fight)
+                .filter(|c| names.contains(&catalog.name(c.ability_id)))
+                .collect();
+            for (i, first) in casts.iter().enumerate() {
+                for second in &casts[i + 1..] {
+                    if second.begin_time >= first.begin_time + duration {
+                        break;
+                    }
+                    if second.source != first.source {
+                        overlaps.push(UltimateOverlap {
+                            family,
+                            time: second.begin_time,
+                            first: (first.source, first.ability_id),
+                            second: (second.source, second.ability_id),
+                        });
+                    }
+                }
+            }
+        }
+        overlaps.sort_by_key(|o| o.time);
+
+        UltimateReport {
+            fight,
+            catalog,
+            players,
+            overlaps,
+        }
+    }
+}
+
+impl Display for UltimateReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let duration = self.fight.duration_ms();
+        writeln!(f, "Fight {} ultimate usage", self.fight.id)?;
+        writeln!(
+            f,
+            "  {:<24} {:>7} {:>7} {:>7} {:>6} {:>7}  Slotted",
+            "Player", "Gained", "Per s", "Spent", "Casts", "Capped"
+        )?;
+        for player in &self.players {
+            writeln!(
+                f,
+                "  {:<24} {:>7} {:>7.2} {:>7} {:>6} {:>6.1}%  {} / {}",
+                self.fight.unit_name(player.unit_id),
+                player.gained,
+                player.gained as f32 / (duration.max(1) as f32 / 1000.0),
+                player.spent,
+                player.casts,
+                percent(player.capped_ms, duration),
+                self.catalog.name(player.slotted[0]),
+                self.catalog.name(player.slotted[1])
+            )?;
+        }
+        for overlap in &self.overlaps {
+            writeln!(
+                f,
+                "  {:>7.1}s {} overlap: {} ({}) on {} ({})",
+                (overlap.time - self.fight.start_time) as f32 / 1000.0,
+                overlap.family,
+                self.fight.unit_name(overlap.second.0),
+                self.catalog.name(overlap.second.1),
+                self.fight.unit_name(overlap.first.0),
+                self.catalog.name(overlap.first.1)
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Display,
+};
+
+use super::resources::ResourceSampler;
+use crate::modules::{
+    catalog::AbilityCatalog,
+    fight::{DeathTracker, Fight, Outcome},
+    log::SegmentType,
+    player::UnitState,
+};
+
+pub const FINAL_WINDOW_MS: usize = 15_000;
+pub const TOP_MECHANICS: usize = 5;
+
+#[derive(Debug)]
+pub struct Death {
+    pub time: usize,
+    pub unit_id: usize,
+    // (source, ability, damage) of the last hit before dying
+    pub killing_blow: Option<(usize, usize, usize)>,
+}
+
+#[derive(Debug)]
+pub struct WipeReport<'a> {
+    fight: &'a Fight<'a>,
+    catalog: &'a AbilityCatalog<'a>,
+    pub deaths: Vec<Death>,
+    // Health % of each boss when the last player died
+    pub boss_health: BTreeMap<usize, f32>,
+    // ability id -> damage dealt to the group in the final window, highest first
+    pub mechanics: Vec<(usize, usize)>,
+}
+
+impl<'a> WipeReport<'a> {
+    // None unless the whole group died
+    pub fn new(
+        fight: &'a Fight<'a>,
+        sampler: &ResourceSampler,
+        catalog: &'a AbilityCatalog<'a>,
+    ) -> Option<Self> {
+        if fight.outcome() != Outcome::Wipe {
+            return None;
+        }
+        let players: BTreeSet<usize> = fight.players().map(|p| p.unit_id).collect();
+        let final_window = fight.end_time.saturating_sub(FINAL_WINDOW_MS);
+        let mut last_hit: HashMap<usize, (usize, usize, usize)> = HashMap::new();
+        let mut mechanics: HashMap<usize, usize> = HashMap::new();
+        let mut tracker = DeathTracker::default();
+        let mut deaths = vec![];
+        for segment in fight.segments {
+            let SegmentType::CombatEvent(event) = &segment.line else {
+                continue;
+            };
+            if let Some(unit_id) = tracker.observe(event).filter(|u| players.contains(u)) {
+                deaths.push(Death {
+                    time: segment.time,
+                    unit_id,
+                    killing_blow: last_hit.remove(&unit_id),
+                });
+            }
+            let Some(target) = event.target.state(&event.source).map(UnitState::unit_id) else {
+                continue;
+            };
+            if !players.contains(&target) || players.contains(&event.source.unit_id()) {
+                continue;
+            }
+            if event.action_result.is_damage() {
+                last_hit.insert(
+                    target,
+                    (event.source.unit_id(), event.ability_id, event.hit_value),
+                );
+                if segment.time >= final_window {
+                    *mechanics.entry(event.ability_id).or_default() += event.hit_value;
+                }
+            }
+        }
+        let mut mechanics: Vec<_> = mechanics.into_iter().collect();
+        mechanics.sort_by_key(|(_, damage)| std::cmp::Reverse(*damage));
+        mechanics.truncate(TOP_MECHANICS);
+        let wiped_at = deaths.last().map_or(fight.end_time, |d| d.time);
+
+        Some(WipeReport {
+            fight,
+            catalog,
+            deaths,
+            boss_health: fight
+                .engaged_bosses()
+                .iter()
+                .filter_map(|b| {
+                    let samples = sampler.health_percent(fight, b.unit_id);
+                    samples
+                        .iter()
+                        .rev()
+                        .find(|(time, _)| *time <= wiped_at)
+                        .map(|(_, hp)| (b.unit_id, *hp))
+                })
+                .collect(),
+            mechanics,
+        })
+    }
+
+    pub fn first_death(&self) -> Option<&Death> {
+        self.deaths.first()
+    }
+}
+
+impl Display for WipeReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let relative = |time: usize| (time - self.fight.start_time) as f32 / 1000.0;
+        writeln!(
+            f,
+            "Fight {} wipe after {:.1}s",
+            self.fight.id,
+            relative(self.fight.end_time)
+        )?;
+        for (boss, hp) in &self.boss_health {
+            writeln!(f, "  {} at {:.1}%", self.fight.unit_name(*boss), hp)?;
+        }
+        if let Some(first) = self.first_death() {
+            writeln!(
+                f,
+                "  First death: {} at {:.1}s",
+                self.fight.unit_name(first.unit_id),
+                relative(first.time)
+            )?;
+        }
+        for death in &self.deaths {
+            write!(
+                f,
+                "    {:>7.1}s {:<24}",
+                relative(death.time),
+                self.fight.unit_name(death.unit_id)
+            )?;
+            match death.killing_blow {
+                Some((source, ability_id, damage)) => writeln!(
+                    f,
+                    " {} by {} ({})",
+                    self.catalog.name(ability_id),
+                    self.fight.unit_name(source),
+                    damage
+                )?,
+                None => writeln!(f, " unknown killing blow")?,
+            }
+        }
+        writeln!(f, "  Most damage in the final {}s", FINAL_WINDOW_MS / 1000)?;
+        for (ability_id, damage) in &self.mechanics {
+            writeln!(
+                f,
+                "    {:<32} {:>10}",
+                self.catalog.name(*ability_id),
+                damage
+            )?;
+        }
+        Ok(())
+    }
+}
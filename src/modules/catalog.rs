@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+
+use super::{
+    abilities::AbilityInfo,
+    log::{Segment, SegmentType},
+};
+
+// Lookup of every ABILITY_INFO seen in the log
+#[derive(Debug, Default)]
+pub struct AbilityCatalog<'a> {
+    abilities: HashMap<usize, &'a AbilityInfo>,
+}
+
+impl<'a> AbilityCatalog<'a> {
+    pub fn new(segments: &'a [Segment]) -> Self {
+        let mut catalog = AbilityCatalog::default();
+        for segment in segments {
+            if let SegmentType::AbilityInfo(info) = &segment.line {
+                catalog.abilities.insert(info.ability_id, info);
+            }
+        }
+        catalog
+    }
+
+    pub fn ability(&self, ability_id: usize) -> Option<&'a AbilityInfo> {
+        self.abilities.get(&ability_id).copied()
+    }
+
+    pub fn name(&self, ability_id: usize) -> &'a str {
+        self.ability(ability_id)
+            .map(|a| a.name.as_ref())
+            .unwrap_or("Unknown")
+    }
+}
@@ -18,7 +18,7 @@ pub struct BeginCast {
     pub target: Targets,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EndReason {
     Completed,
     PlayerCancelled,
@@ -44,7 +44,7 @@ pub struct EndCast {
     pub interrupting_unit_id: Option<usize>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ActionResult {
     AbilityOnCooldown,
     Absorbed,
@@ -157,6 +157,39 @@ pub enum ActionResult {
     WrongWeapon,
 }
 
+impl ActionResult {
+    // Results that took health from the target
+    pub fn is_damage(&self) -> bool {
+        matches!(
+            self,
+            Self::Damage
+                | Self::CriticalDamage
+                | Self::DotTick
+                | Self::DotTickCritical
+                | Self::BlockedDamage
+                | Self::PreciseDamage
+                | Self::WreckingDamage
+                | Self::FallDamage
+        )
+    }
+
+    // Results that restored health to the target
+    pub fn is_heal(&self) -> bool {
+        matches!(
+            self,
+            Self::Heal | Self::CriticalHeal | Self::HotTick | Self::HotTickCritical
+        )
+    }
+
+    // Results logged against the unit that died
+    pub fn is_death(&self) -> bool {
+        matches!(
+            self,
+            Self::Died | Self::DiedXp | Self::DiedCompanionXp | Self::KillingBlow
+        )
+    }
+}
+
 impl From<String> for ActionResult {
     fn from(value: String) -> Self {
         match value.as_str() {
@@ -312,7 +345,7 @@ impl From<String> for DamageType {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum PowerType {
     Adrenaline = 8,
     Charges = 5,
@@ -352,7 +385,7 @@ impl From<String> for PowerType {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EffectChangeType {
     Faded,
     Gained,
@@ -61,7 +61,7 @@ pub struct UnitRemoved {
     pub unit_id: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UnitType {
     Player,
     Monster,
@@ -79,7 +79,7 @@ impl From<String> for UnitType {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PlayerReaction {
     PlayerAlly,
     Friendly,
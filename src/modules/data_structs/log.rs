@@ -16,25 +16,118 @@ pub struct BeginLog {
 }
 
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MapInfo {
     pub id: usize,
     pub name: Arc<str>,
     pub texture_path: PathBuf,
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum DungeonDifficulty {
     Normal,
     Veteran,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ZoneInfo {
     pub id: usize,
     pub name: Arc<str>,
     pub dungeon_difficulty: DungeonDifficulty,
 }
 
+// Zone ids of trials, arenas and dungeons with the short names players use for them
+pub const KNOWN_ZONES: [(usize, &str); 71] = [
+    (11, "VoM"),
+    (22, "VF"),
+    (31, "SW"),
+    (63, "DC1"),
+    (64, "BC"),
+    (126, "EH1"),
+    (130, "CoH1"),
+    (131, "TI"),
+    (144, "SC1"),
+    (146, "WS1"),
+    (148, "AC"),
+    (176, "CoA1"),
+    (283, "FG1"),
+    (380, "BC1"),
+    (449, "DK"),
+    (635, "DSA"),
+    (636, "HRC"),
+    (638, "AA"),
+    (639, "SO"),
+    (677, "MA"),
+    (678, "ICP"),
+    (681, "CoA2"),
+    (688, "WGT"),
+    (725, "MoL"),
+    (843, "RoM"),
+    (848, "CoS"),
+    (930, "DC2"),
+    (931, "EH2"),
+    (932, "CoH2"),
+    (933, "WS2"),
+    (934, "FG2"),
+    (935, "BC2"),
+    (936, "SC2"),
+    (973, "BF"),
+    (974, "FH"),
+    (975, "HoF"),
+    (1000, "AS"),
+    (1009, "FL"),
+    (1010, "SCP"),
+    (1051, "CR"),
+    (1052, "MHK"),
+    (1055, "MoS"),
+    (1080, "FV"),
+    (1081, "DoM"),
+    (1082, "BRP"),
+    (1121, "SS"),
+    (1122, "MGF"),
+    (1123, "LoM"),
+    (1152, "IR"),
+    (1153, "UG"),
+    (1196, "KA"),
+    (1197, "SG"),
+    (1201, "CT"),
+    (1227, "VH"),
+    (1228, "BDV"),
+    (1229, "CD"),
+    (1263, "RG"),
+    (1267, "RPB"),
+    (1268, "DC"),
+    (1301, "CA"),
+    (1302, "SR"),
+    (1344, "DSR"),
+    (1360, "ERE"),
+    (1361, "GD"),
+    (1389, "BS"),
+    (1390, "SH"),
+    (1427, "SE"),
+    (1436, "IA"),
+    (1470, "OP"),
+    (1471, "BV"),
+    (1478, "LC"),
+];
+
+impl ZoneInfo {
+    pub fn short_name(&self) -> Option<&'static str> {
+        KNOWN_ZONES
+            .iter()
+            .find(|(id, _)| *id == self.id)
+            .map(|(_, name)| *name)
+    }
+
+    // vAS, nKA or the full zone name when it is not a known trial, arena or dungeon
+    pub fn label(&self) -> String {
+        match (self.short_name(), self.dungeon_difficulty) {
+            (Some(name), DungeonDifficulty::Veteran) => format!("v{name}"),
+            (Some(name), DungeonDifficulty::Normal) => format!("n{name}"),
+            (None, _) => self.name.to_string(),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Trialinit {
     pub id: u8,
@@ -61,6 +154,28 @@ pub struct EndTrial {
     pub final_vitality_bonus: u8,
 }
 
+#[derive(Debug)]
+pub struct BeginEndlessDungeon {
+    pub id: usize,
+}
+
+#[derive(Debug)]
+pub struct EndEndlessDungeon {
+    pub id: usize,
+    pub duration_ms: usize,
+}
+
+#[derive(Debug)]
+pub struct EndlessDungeonBuff {
+    pub id: usize,
+    pub ability_id: usize,
+}
+
+#[derive(Debug)]
+pub struct EndlessDungeonStageEnd {
+    pub id: usize,
+}
+
 #[derive(Debug)]
 pub enum SegmentType {
     BeginLog(BeginLog),
@@ -83,12 +198,19 @@ pub enum SegmentType {
     TrialInit(Trialinit),
     BeginTrial(BeginTrial),
     EndTrial(EndTrial),
-    EndlessDungeonBuffRemove,
-    EndlessDungeonBuffAdd,
-    EndlessDungeonStageEnd,
+    BeginEndlessDungeon(BeginEndlessDungeon),
+    EndEndlessDungeon(EndEndlessDungeon),
+    EndlessDungeonBuffRemove(EndlessDungeonBuff),
+    EndlessDungeonBuffAdd(EndlessDungeonBuff),
+    EndlessDungeonStageEnd(EndlessDungeonStageEnd),
 }
 #[derive(Debug)]
 pub struct Segment {
     pub time: usize, // Time Since Logging Began in MS
     pub line: SegmentType,
+    // Number of BEGIN_LOGs up to here, times and unit ids restart with every session
+    pub session: usize,
+    // Latest ZONE_CHANGED and MAP_CHANGED of the log at this segment
+    pub zone: Option<Arc<ZoneInfo>>,
+    pub map: Option<Arc<MapInfo>>,
 }
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::{collections::VecDeque, fmt::Debug};
 
 use crate::modules::parser::parse_bool;
 
@@ -73,6 +73,43 @@ impl UnitState {
             heading,
         }
     }
+
+    pub fn unit_id(&self) -> usize {
+        self.unit_id
+    }
+
+    pub fn health(&self) -> (usize, usize) {
+        self.health
+    }
+
+    pub fn magicka(&self) -> (usize, usize) {
+        self.magicka
+    }
+
+    pub fn stamina(&self) -> (usize, usize) {
+        self.stamina
+    }
+
+    pub fn ultimate(&self) -> (u16, u16) {
+        self.ultimate
+    }
+
+    pub fn werewolf(&self) -> (u16, u16) {
+        self.werewolf
+    }
+
+    pub fn shield(&self) -> usize {
+        self.shield
+    }
+
+    // Normalized map coordinates
+    pub fn position(&self) -> (f32, f32) {
+        self.position
+    }
+
+    pub fn heading(&self) -> f32 {
+        self.heading
+    }
 }
 
 //<equipmentInfo> refers to the following fields for a piece of equipment: slot, id, isCP, level, trait, displayQuality, setId, enchantType, isEnchantCP, enchantLevel, enchantQuality.
@@ -94,10 +131,77 @@ pub struct Equipment {
     pub backup_poison: Option<EquipmentInfo<PoisonTrait, PoisonEnchantType>>,
 }
 
+// One equipped piece with its trait and enchant flattened to their names,
+// `weight` is how many pieces it counts as towards its set
+#[derive(Debug)]
+pub struct GearPiece<'a> {
+    pub slot: &'static str,
+    pub set_id: usize,
+    pub level: &'a EquipmentLevel,
+    pub quality: &'a Quality,
+    pub r#trait: String,
+    pub enchant: Option<(String, &'a EquipmentLevel, &'a Quality)>,
+    pub weight: usize,
+    pub front_bar: bool,
+    pub back_bar: bool,
+}
+
+impl Equipment {
+    pub fn pieces(&self) -> Vec<GearPiece<'_>> {
+        let mut pieces = vec![];
+        let armor = [
+            ("Head", &self.head),
+            ("Shoulders", &self.shoulders),
+            ("Chest", &self.chest),
+            ("Hand", &self.hand),
+            ("Waist", &self.waist),
+            ("Legs", &self.legs),
+            ("Feet", &self.feet),
+        ];
+        for (slot, piece) in armor {
+            pieces.extend(piece.as_ref().map(|p| p.piece(slot, 1, true, true)));
+        }
+        for (slot, piece) in [
+            ("Neck", &self.neck),
+            ("Ring 1", &self.ring1),
+            ("Ring 2", &self.ring2),
+        ] {
+            pieces.extend(piece.as_ref().map(|p| p.piece(slot, 1, true, true)));
+        }
+        for (hand, front) in [(&self.main, true), (&self.backup, false)] {
+            let slot = if front {
+                "Main hand"
+            } else {
+                "Backup main hand"
+            };
+            match hand {
+                Some(WeaponHand::TwoHand(weapon)) => {
+                    pieces.push(weapon.piece(slot, 2, front, !front))
+                }
+                Some(WeaponHand::OneHand(main, off)) => {
+                    let off_slot = if front { "Off hand" } else { "Backup off hand" };
+                    pieces.extend(main.as_ref().map(|w| w.piece(slot, 1, front, !front)));
+                    match off {
+                        Some(Either::Left(weapon)) => {
+                            pieces.push(weapon.piece(off_slot, 1, front, !front))
+                        }
+                        Some(Either::Right(shield)) => {
+                            pieces.push(shield.piece(off_slot, 1, front, !front))
+                        }
+                        None => (),
+                    }
+                }
+                None => (),
+            }
+        }
+        pieces
+    }
+}
+
 impl<T, V> EquipmentInfo<T, V>
 where
-    T: TraitMarker + From<String>,
-    V: EnchantMarker,
+    T: TraitMarker + From<String> + Debug,
+    V: EnchantMarker + Debug,
 {
     pub fn parse_equipment(tokens: &mut VecDeque<String>) -> Self {
         let id = tokens.pop_front().unwrap().parse().unwrap();
@@ -121,6 +225,29 @@ where
             enchant,
         }
     }
+
+    fn piece(
+        &self,
+        slot: &'static str,
+        weight: usize,
+        front_bar: bool,
+        back_bar: bool,
+    ) -> GearPiece<'_> {
+        GearPiece {
+            slot,
+            set_id: self.set_id,
+            level: &self.level,
+            quality: &self.display_quality,
+            r#trait: format!("{:?}", self.r#trait),
+            enchant: self
+                .enchant
+                .as_ref()
+                .map(|e| (format!("{:?}", e.r#type), &e.level, &e.quality)),
+            weight,
+            front_bar,
+            back_bar,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -148,34 +275,37 @@ impl WeaponHand {
         tokens.iter().any(|v| v.contains("ARMOR_"))
     }
 
+    // Main and off hand slots of either bar, in whatever order they are logged
     pub fn parse_weapon(ident: &str, tokens: &mut VecDeque<String>, current: Option<Self>) -> Self {
+        let (main, off) = match current {
+            Some(Self::OneHand(main, off)) => (main, off),
+            Some(Self::TwoHand(weapon)) => (Some(weapon), None),
+            None => (None, None),
+        };
         match ident {
-            "BACKUP_MAIN" | "MAIN" => Self::TwoHand(EquipmentInfo::parse_equipment(tokens)),
-            "OFF_HAND" | "MAIN_HAND" => {
-                if let Some(current_hand) = current {
-                    match current_hand {
-                        Self::OneHand(mut a, mut b) => {
-                            if a.is_none() {
-                                a = Some(EquipmentInfo::parse_equipment(tokens));
-                            } else if b.is_none() {
-                                // Checks if has shield
-                                if Self::check_if_armor(tokens) {
-                                    b = Some(Either::Right(EquipmentInfo::parse_equipment(tokens)))
-                                } else {
-                                    b = Some(Either::Left(EquipmentInfo::parse_equipment(tokens)));
-                                }
-                            }
-                            WeaponHand::OneHand(a, b)
-                        }
-                        WeaponHand::TwoHand(_) => unreachable!(),
-                    }
+            "MAIN_HAND" | "BACKUP_MAIN" | "MAIN" => {
+                Self::OneHand(Some(EquipmentInfo::parse_equipment(tokens)), off)
+            }
+            "OFF_HAND" | "BACKUP_OFF" => {
+                // Checks if has shield
+                let off = if Self::check_if_armor(tokens) {
+                    Either::Right(EquipmentInfo::parse_equipment(tokens))
                 } else {
-                    WeaponHand::OneHand(Some(EquipmentInfo::parse_equipment(tokens)), None)
-                }
+                    Either::Left(EquipmentInfo::parse_equipment(tokens))
+                };
+                Self::OneHand(main, Some(off))
             }
             _ => unreachable!(),
         }
     }
+
+    // The log has no weapon type, a bar with a main hand and an empty off hand holds a two hander
+    pub fn finish(self) -> Self {
+        match self {
+            Self::OneHand(Some(weapon), None) => Self::TwoHand(weapon),
+            hand => hand,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -471,6 +601,15 @@ pub enum Targets {
 }
 
 impl Targets {
+    // Resolves the targeted unit, `*` in the log means the source targeted itself
+    pub fn state<'a>(&'a self, source: &'a UnitState) -> Option<&'a UnitState> {
+        match self {
+            Targets::SelfTarget => Some(source),
+            Targets::Target(state) => Some(state),
+            Targets::None => None,
+        }
+    }
+
     pub fn parse_target_unit(tokens: &mut VecDeque<String>) -> Targets {
         let unit_id = tokens.pop_front().unwrap();
         if unit_id == "0" {
@@ -566,7 +705,7 @@ impl Race {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Class {
     Arcanist = 117,
     Templar = 6,
@@ -0,0 +1,365 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{
+    combat::{CombatEvent, UnitAdded, UnitType},
+    log::{MapInfo, Segment, SegmentType, ZoneInfo},
+    player::PlayerInfo,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    // Every boss died, at the given time
+    Kill(usize),
+    // Every group member was dead when combat ended
+    Wipe,
+    // Combat ended some other way, e.g. the group ran out or a trash pull
+    Reset,
+}
+
+// One death per unit, a death is logged as several results (DIED, DIED_XP, KILLING_BLOW)
+// and the unit stays dead until it shows health again
+#[derive(Debug, Default)]
+pub struct DeathTracker {
+    dead: BTreeSet<usize>,
+}
+
+impl DeathTracker {
+    // The unit that died with this event, unless it already was dead
+    pub fn observe(&mut self, event: &CombatEvent) -> Option<usize> {
+        for state in [Some(&event.source), event.target.state(&event.source)]
+            .into_iter()
+            .flatten()
+        {
+            if state.health().0 > 0 {
+                self.dead.remove(&state.unit_id());
+            }
+        }
+        if !event.action_result.is_death() {
+            return None;
+        }
+        let target = event.target.state(&event.source)?.unit_id();
+        self.dead.insert(target).then_some(target)
+    }
+
+    pub fn is_dead(&self, unit_id: usize) -> bool {
+        self.dead.contains(&unit_id)
+    }
+}
+
+// A single pull, everything between BEGIN_COMBAT and END_COMBAT
+#[derive(Debug)]
+pub struct Fight<'a> {
+    pub id: usize,
+    // Logging session the fight was recorded in, see Segment::session
+    pub session: usize,
+    // 1 for the first attempt at this boss, zone and difficulty, counting up with every retry
+    pub pull: usize,
+    pub start_time: usize,
+    pub end_time: usize,
+    pub segments: &'a [Segment],
+    // Every unit known while the fight was running, keyed by unit id
+    pub units: BTreeMap<usize, &'a UnitAdded>,
+    // Latest PLAYER_INFO of each player by the end of the fight
+    pub player_info: BTreeMap<usize, &'a PlayerInfo>,
+    // Group members left out of per-player reports, they still count towards the outcome
+    pub hidden_players: BTreeSet<usize>,
+}
+
+impl<'a> Fight<'a> {
+    pub fn split(segments: &'a [Segment]) -> Vec<Fight<'a>> {
+        let mut fights = vec![];
+        let mut units: BTreeMap<usize, &UnitAdded> = BTreeMap::new();
+        let mut player_info: BTreeMap<usize, &PlayerInfo> = BTreeMap::new();
+        let mut current: Option<(usize, BTreeMap<usize, &UnitAdded>)> = None;
+        for (index, segment) in segments.iter().enumerate() {
+            match &segment.line {
+                SegmentType::BeginCombat => {
+                    current = Some((index, units.clone()));
+                }
+                SegmentType::EndCombat => {
+                    if let Some((start, fight_units)) = current.take() {
+                        fights.push(Fight::new(
+                            fights.len(),
+                            &segments[start..=index],
+                            fight_units,
+                            player_info.clone(),
+                        ));
+                    }
+                }
+                SegmentType::BeginLog(_) => {
+                    // Logging restarted mid combat, times restart with the new session
+                    if let Some((start, fight_units)) = current.take() {
+                        fights.push(Fight::new(
+                            fights.len(),
+                            &segments[start..index],
+                            fight_units,
+                            player_info.clone(),
+                        ));
+                    }
+                    units.clear();
+                    player_info.clear();
+                }
+                SegmentType::PlayerInfo(info) => {
+                    player_info.insert(info.unit_id, info);
+                }
+                SegmentType::UnitAdded(unit) => {
+                    units.insert(unit.unit_id, unit);
+                    if let Some((_, fight_units)) = current.as_mut() {
+                        fight_units.insert(unit.unit_id, unit);
+                    }
+                }
+                SegmentType::UnitRemoved(unit) => {
+                    units.remove(&unit.unit_id);
+                }
+                _ => (),
+            }
+        }
+        // Logging stopped mid combat
+        if let Some((start, fight_units)) = current {
+            fights.push(Fight::new(
+                fights.len(),
+                &segments[start..],
+                fight_units,
+                player_info,
+            ));
+        }
+        // (zone id and difficulty, boss) -> pulls so far
+        let mut attempts = BTreeMap::new();
+        for fight in fights.iter_mut() {
+            let key = (
+                fight.zone().map(|z| (z.id, z.dungeon_difficulty)),
+                fight.boss_name(),
+            );
+            let pull = attempts.entry(key).or_default();
+            *pull += 1;
+            fight.pull = *pull;
+        }
+        fights
+    }
+
+    fn new(
+        id: usize,
+        segments: &'a [Segment],
+        units: BTreeMap<usize, &'a UnitAdded>,
+        player_info: BTreeMap<usize, &'a PlayerInfo>,
+    ) -> Self {
+        Fight {
+            id,
+            session: segments.first().map(|s| s.session).unwrap_or_default(),
+            pull: 1,
+            start_time: segments.first().map(|s| s.time).unwrap_or_default(),
+            end_time: segments.last().map(|s| s.time).unwrap_or_default(),
+            segments,
+            units,
+            player_info,
+            hidden_players: BTreeSet::new(),
+        }
+    }
+
+    // The part of the fight between start and end, e.g. a single phase
+    pub fn slice(&self, start: usize, end: usize) -> Fight<'a> {
+        let from = self.segments.partition_point(|s| s.time < start);
+        let to = self.segments.partition_point(|s| s.time <= end);
+        Fight {
+            id: self.id,
+            session: self.session,
+            pull: self.pull,
+            start_time: start.max(self.start_time),
+            end_time: end.min(self.end_time),
+            segments: &self.segments[from..to.max(from)],
+            units: self.units.clone(),
+            player_info: self.player_info.clone(),
+            hidden_players: self.hidden_players.clone(),
+        }
+    }
+
+    // The same fight with only the players matching the filter left in per-player reports
+    pub fn with_players(&self, keep: impl Fn(&UnitAdded) -> bool) -> Fight<'a> {
+        let hidden: BTreeSet<usize> = self
+            .group()
+            .filter(|p| !keep(p))
+            .map(|p| p.unit_id)
+            .collect();
+        Fight {
+            id: self.id,
+            session: self.session,
+            pull: self.pull,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            segments: self.segments,
+            units: self.units.clone(),
+            player_info: self
+                .player_info
+                .iter()
+                .filter(|(unit_id, _)| !hidden.contains(unit_id))
+                .map(|(unit_id, info)| (*unit_id, *info))
+                .collect(),
+            hidden_players: hidden,
+        }
+    }
+
+    pub fn zone(&self) -> Option<&'a ZoneInfo> {
+        self.segments.first()?.zone.as_deref()
+    }
+
+    pub fn map(&self) -> Option<&'a MapInfo> {
+        self.segments.first()?.map.as_deref()
+    }
+
+    // The first boss engaged in the fight, trash pulls have none
+    pub fn boss_name(&self) -> &'a str {
+        self.engaged_bosses()
+            .first()
+            .map_or("Trash", |b| b.name.as_ref())
+    }
+
+    // e.g. "vAS Saint Olms the Just, pull 3"
+    pub fn label(&self) -> String {
+        match self.zone() {
+            Some(zone) => format!("{} {}, pull {}", zone.label(), self.boss_name(), self.pull),
+            None => format!("{}, pull {}", self.boss_name(), self.pull),
+        }
+    }
+
+    pub fn duration_ms(&self) -> usize {
+        self.end_time - self.start_time
+    }
+
+    pub fn unit_name(&self, unit_id: usize) -> &str {
+        self.units
+            .get(&unit_id)
+            .map(|u| u.name.as_ref())
+            .unwrap_or("Unknown")
+    }
+
+    // Players in the local player's group, including the local player and hidden players
+    pub fn group(&self) -> impl Iterator<Item = &'a UnitAdded> + '_ {
+        self.units.values().copied().filter(|u| {
+            u.unit_type == UnitType::Player && (u.is_local_player || u.is_grouped_with_local_player)
+        })
+    }
+
+    pub fn players(&self) -> impl Iterator<Item = &'a UnitAdded> + '_ {
+        self.group()
+            .filter(|u| !self.hidden_players.contains(&u.unit_id))
+    }
+
+    pub fn bosses(&self) -> impl Iterator<Item = &'a UnitAdded> + '_ {
+        self.units.values().copied().filter(|u| u.is_boss)
+    }
+
+    // Bosses that took part in combat events, in the order they first did. Bosses of earlier
+    // pulls and bystanders stay in `units` without ever showing up
+    pub fn engaged_bosses(&self) -> Vec<&'a UnitAdded> {
+        let mut engaged: Vec<&UnitAdded> = vec![];
+        for segment in self.segments {
+            let SegmentType::CombatEvent(event) = &segment.line else {
+                continue;
+            };
+            for state in [Some(&event.source), event.target.state(&event.source)]
+                .into_iter()
+                .flatten()
+            {
+                let Some(unit) = self.units.get(&state.unit_id()) else {
+                    continue;
+                };
+                if unit.is_boss && !engaged.iter().any(|b| b.unit_id == unit.unit_id) {
+                    engaged.push(unit);
+                }
+            }
+        }
+        engaged
+    }
+
+    pub fn outcome(&self) -> Outcome {
+        let bosses: BTreeSet<usize> = self.engaged_bosses().iter().map(|b| b.unit_id).collect();
+        let players: BTreeSet<usize> = self.group().map(|p| p.unit_id).collect();
+        let mut deaths = DeathTracker::default();
+        let mut dead_bosses = BTreeSet::new();
+        let mut last_boss_death = self.start_time;
+        for segment in self.segments {
+            let SegmentType::CombatEvent(event) = &segment.line else {
+                continue;
+            };
+            if let Some(unit_id) = deaths.observe(event) {
+                if bosses.contains(&unit_id) {
+                    dead_bosses.insert(unit_id);
+                    last_boss_death = segment.time;
+                }
+            }
+        }
+        if !bosses.is_empty() && dead_bosses == bosses {
+            Outcome::Kill(last_boss_death)
+        } else if !players.is_empty() && players.iter().all(|p| deaths.is_dead(*p)) {
+            Outcome::Wipe
+        } else {
+            Outcome::Reset
+        }
+    }
+
+    pub fn time_to_kill(&self) -> Option<usize> {
+        match self.outcome() {
+            Outcome::Kill(time) => Some(time - self.start_time),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::parser::lex;
+
+    #[test]
+    fn begin_log_closes_open_fight() {
+        let segments = lex(concat!(
+            "0,BEGIN_LOG,1700000000000,15,\"NA Megaserver\",\"en\",\"eso.live.10.0.0\"\n",
+            "5000,BEGIN_COMBAT\n",
+            "0,BEGIN_LOG,1700000100000,15,\"NA Megaserver\",\"en\",\"eso.live.10.0.0\"\n",
+            "100,END_COMBAT\n",
+        ));
+        let fights = Fight::split(&segments);
+        assert_eq!(fights.len(), 1);
+        assert_eq!(fights[0].session, 1);
+        assert_eq!((fights[0].start_time, fights[0].end_time), (5000, 5000));
+        assert_eq!(fights[0].duration_ms(), 0);
+    }
+
+    fn state(unit_id: usize, health: usize) -> String {
+        format!("{unit_id},{health}/30000,20000/20000,15000/15000,100/500,0/1000,0,0.5,0.5,1.2")
+    }
+
+    #[test]
+    fn bystander_bosses_are_not_engaged() {
+        let log = [
+            "0,BEGIN_LOG,1700000000000,15,\"NA Megaserver\",\"en\",\"eso.live.10.0.0\"".into(),
+            "1,UNIT_ADDED,1,PLAYER,T,1,0,F,6,3,\"Solo\",\"@solo\",1001,50,2000,0,PLAYER_ALLY,T"
+                .into(),
+            "1,UNIT_ADDED,2,MONSTER,F,0,1,T,0,0,\"Old Boss\",\"\",0,50,160,0,HOSTILE,F".into(),
+            "1,UNIT_ADDED,3,MONSTER,F,0,2,T,0,0,\"New Boss\",\"\",0,50,160,0,HOSTILE,F".into(),
+            "1000,BEGIN_COMBAT".into(),
+            format!(
+                "1500,COMBAT_EVENT,DAMAGE,FIRE,0,1000,0,0,100,{},{}",
+                state(1, 30000),
+                state(3, 0)
+            ),
+            format!(
+                "1500,COMBAT_EVENT,DIED,GENERIC,0,0,0,0,100,{},{}",
+                state(1, 30000),
+                state(3, 0)
+            ),
+            "2000,END_COMBAT".into(),
+        ]
+        .join("\n");
+        let segments = lex(&log);
+        let fights = Fight::split(&segments);
+        let engaged: Vec<_> = fights[0]
+            .engaged_bosses()
+            .iter()
+            .map(|b| b.unit_id)
+            .collect();
+        assert_eq!(engaged, [3]);
+        assert_eq!(fights[0].outcome(), Outcome::Kill(1500));
+        assert_eq!(fights[0].boss_name(), "New Boss");
+    }
+}
@@ -1,3 +1,7 @@
+pub mod analysis;
+pub mod catalog;
+pub mod fight;
 pub mod parser;
+pub mod render;
 mod data_structs;
 use data_structs::*;
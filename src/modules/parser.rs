@@ -11,6 +11,9 @@ pub fn parse_bool(b: &str) -> bool {
 
 pub struct Lexer {
     data: VecDeque<Arc<str>>,
+    session: usize,
+    zone: Option<Arc<ZoneInfo>>,
+    map: Option<Arc<MapInfo>>,
 }
 
 impl Lexer {
@@ -22,6 +25,9 @@ impl Lexer {
                     Err(_) => None,
                 })
                 .collect::<VecDeque<_>>(),
+            session: 0,
+            zone: None,
+            map: None,
         }
     }
 
@@ -424,7 +430,12 @@ impl Lexer {
                                     equipment_info.main,
                                 ));
                             }
-                            x if ["BACKUP_MAIN"].contains(&x) => {
+                            "BACKUP_POISON" => {
+                                equipment_info.backup_poison = Some(
+                                    EquipmentInfo::parse_equipment(&mut equipment_piece_tokens),
+                                );
+                            }
+                            x if ["BACKUP_MAIN", "BACKUP_OFF"].contains(&x) => {
                                 equipment_info.backup = Some(WeaponHand::parse_weapon(
                                     x,
                                     &mut equipment_piece_tokens,
@@ -437,6 +448,8 @@ impl Lexer {
                             ),
                         }
                     }
+                    equipment_info.main = equipment_info.main.map(WeaponHand::finish);
+                    equipment_info.backup = equipment_info.backup.map(WeaponHand::finish);
 
                     let mut primary_ability_id = Self::tokenize(&tokens.pop_front().unwrap())
                         .iter()
@@ -456,25 +469,62 @@ impl Lexer {
                         backup_ability_id: backup_ability_id.try_into().unwrap(),
                     }))
                 }
+                "ENDLESS_DUNGEON_BEGIN" => {
+                    let id = tokens.pop_front().unwrap().parse().unwrap();
+                    // Start time since the epoch, the run's END carries its length
+                    tokens.pop_front();
+                    SegmentType::BeginEndlessDungeon(BeginEndlessDungeon { id })
+                }
+                "ENDLESS_DUNGEON_END" => {
+                    let id = tokens.pop_front().unwrap().parse().unwrap();
+                    let duration_ms = tokens.pop_front().unwrap().parse().unwrap();
+                    SegmentType::EndEndlessDungeon(EndEndlessDungeon { id, duration_ms })
+                }
                 "ENDLESS_DUNGEON_BUFF_ADDED" => {
-                    eprintln!("Infinite Archive Buff Added Not Handled");
-                    SegmentType::EndlessDungeonBuffAdd
+                    let id = tokens.pop_front().unwrap().parse().unwrap();
+                    let ability_id = tokens.pop_front().unwrap().parse().unwrap();
+                    SegmentType::EndlessDungeonBuffAdd(EndlessDungeonBuff { id, ability_id })
                 }
                 "ENDLESS_DUNGEON_STAGE_END" => {
-                    eprintln!("Infinite Archive Stage End Not Handled");
-                    SegmentType::EndlessDungeonStageEnd
+                    let id = tokens.pop_front().unwrap().parse().unwrap();
+                    SegmentType::EndlessDungeonStageEnd(EndlessDungeonStageEnd { id })
                 }
                 "ENDLESS_DUNGEON_BUFF_REMOVED" => {
-                    eprintln!("Infinite Archive Buffs Removal Not Handled");
-                    SegmentType::EndlessDungeonBuffRemove
+                    let id = tokens.pop_front().unwrap().parse().unwrap();
+                    let ability_id = tokens.pop_front().unwrap().parse().unwrap();
+                    SegmentType::EndlessDungeonBuffRemove(EndlessDungeonBuff { id, ability_id })
                 }
                 x => {
                     todo!("{x} is not implemented!(): {:#?}", tokens);
                 }
             };
-            Some(Segment { time, line })
+            match &line {
+                SegmentType::BeginLog(_) => {
+                    self.session += 1;
+                    self.zone = None;
+                    self.map = None;
+                }
+                SegmentType::ZoneInfo(zone) => self.zone = Some(Arc::new(zone.clone())),
+                SegmentType::MapInfo(map) => self.map = Some(Arc::new(map.clone())),
+                _ => (),
+            }
+            Some(Segment {
+                time,
+                line,
+                session: self.session,
+                zone: self.zone.clone(),
+                map: self.map.clone(),
+            })
         } else {
             None
         }
     }
 }
+
+// Every segment of a log given as text
+#[cfg(test)]
+pub fn lex(log: &str) -> Vec<Segment> {
+    use std::io::BufRead;
+    let mut lexer = Lexer::new(log.as_bytes().lines());
+    std::iter::from_fn(|| lexer.next_segment()).collect()
+}
@@ -0,0 +1,388 @@
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{self, BufWriter},
+    path::Path,
+};
+
+use super::{
+    analysis::positions::PositionTracks,
+    fight::Fight,
+    log::{MapInfo, SegmentType},
+};
+
+pub const IMAGE_SIZE: u32 = 800;
+pub const HEATMAP_CELLS: usize = 64;
+
+// Share of the drawn area left empty around the outermost positions
+const PADDING: f32 = 0.1;
+// Smallest share of the map drawn, keeps a group standing still from filling the image
+const MIN_WINDOW: f32 = 0.005;
+const BACKGROUND: Rgb = (32, 32, 32);
+// Pixels per glyph pixel, brings the 5x7 glyphs close to the SVG font size
+const GLYPH_SCALE: i64 = 2;
+const PALETTE: [Rgb; 8] = [
+    (230, 25, 75),
+    (60, 180, 75),
+    (255, 225, 25),
+    (67, 99, 216),
+    (245, 130, 49),
+    (145, 30, 180),
+    (70, 240, 240),
+    (240, 50, 230),
+];
+
+type Rgb = (u8, u8, u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Svg,
+    Png,
+}
+
+impl ImageFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format.to_lowercase().as_str() {
+            "svg" => Some(ImageFormat::Svg),
+            "png" => Some(ImageFormat::Png),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Svg => "svg",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Shape {
+    // Pixel coordinates
+    Line(Vec<(f32, f32)>, Rgb),
+    Rect(f32, f32, f32, f32, Rgb, f32),
+    // Left end of the baseline, PNG draws it uppercase with the built in glyphs
+    Text(f32, f32, String, Rgb),
+}
+
+// Square window of normalized map coordinates that is drawn
+#[derive(Debug, Clone, Copy)]
+struct Bounds {
+    min_x: f32,
+    min_y: f32,
+    size: f32,
+}
+
+impl Bounds {
+    fn around(points: impl Iterator<Item = (f32, f32)>) -> Self {
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (1.0_f32, 1.0_f32, 0.0_f32, 0.0_f32);
+        for (x, y) in points {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+        if min_x > max_x {
+            // Nothing to draw, show the whole map
+            return Bounds {
+                min_x: 0.0,
+                min_y: 0.0,
+                size: 1.0,
+            };
+        }
+        let size = (max_x - min_x).max(max_y - min_y).max(MIN_WINDOW) * (1.0 + 2.0 * PADDING);
+        Bounds {
+            min_x: (min_x + max_x - size) / 2.0,
+            min_y: (min_y + max_y - size) / 2.0,
+            size,
+        }
+    }
+
+    fn project(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        let scale = IMAGE_SIZE as f32 / self.size;
+        ((x - self.min_x) * scale, (y - self.min_y) * scale)
+    }
+}
+
+// Shapes in image space, written out as SVG or rasterized to PNG
+#[derive(Debug)]
+pub struct Scene {
+    title: String,
+    // Map texture placed under the shapes in SVG, (path, x, y, size)
+    background: Option<(String, f32, f32, f32)>,
+    shapes: Vec<Shape>,
+}
+
+impl Scene {
+    fn new(title: String, bounds: &Bounds, map: Option<&MapInfo>) -> Self {
+        // Normalized coordinates span the whole map texture. The log names it by its path
+        // inside the game, so it is only used once converted to a .png at that path here
+        let background = map.and_then(|m| {
+            let path = std::fs::canonicalize(m.texture_path.with_extension("png")).ok()?;
+            let (x, y) = bounds.project((0.0, 0.0));
+            let size = IMAGE_SIZE as f32 / bounds.size;
+            Some((path.display().to_string(), x, y, size))
+        });
+        Scene {
+            title,
+            background,
+            shapes: vec![],
+        }
+    }
+
+    pub fn save(&self, path: &Path, format: ImageFormat) -> io::Result<()> {
+        match format {
+            ImageFormat::Svg => std::fs::write(path, self.to_svg()),
+            ImageFormat::Png => {
+                let mut encoder =
+                    png::Encoder::new(BufWriter::new(File::create(path)?), IMAGE_SIZE, IMAGE_SIZE);
+                encoder.set_color(png::ColorType::Rgb);
+                encoder.set_depth(png::BitDepth::Eight);
+                let mut writer = encoder.write_header()?;
+                writer.write_image_data(&self.to_pixels())?;
+                Ok(())
+            }
+        }
+    }
+
+    pub fn to_svg(&self) -> String {
+        let color = |(r, g, b): Rgb| format!("rgb({r},{g},{b})");
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{IMAGE_SIZE}\" height=\"{IMAGE_SIZE}\">\n<title>{}</title>\n<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+            escape(&self.title),
+            color(BACKGROUND)
+        );
+        if let Some((path, x, y, size)) = &self.background {
+            let _ = writeln!(
+                svg,
+                "<image href=\"{}\" x=\"{x:.1}\" y=\"{y:.1}\" width=\"{size:.1}\" height=\"{size:.1}\" opacity=\"0.5\"/>",
+                escape(path)
+            );
+        }
+        for shape in &self.shapes {
+            let _ = match shape {
+                Shape::Line(points, rgb) => {
+                    let points: Vec<_> = points
+                        .iter()
+                        .map(|(x, y)| format!("{x:.1},{y:.1}"))
+                        .collect();
+                    writeln!(
+                        svg,
+                        "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+                        points.join(" "),
+                        color(*rgb)
+                    )
+                }
+                Shape::Rect(x, y, width, height, rgb, opacity) => writeln!(
+                    svg,
+                    "<rect x=\"{x:.1}\" y=\"{y:.1}\" width=\"{width:.1}\" height=\"{height:.1}\" fill=\"{}\" fill-opacity=\"{opacity:.2}\"/>",
+                    color(*rgb)
+                ),
+                Shape::Text(x, y, text, rgb) => writeln!(
+                    svg,
+                    "<text x=\"{x:.1}\" y=\"{y:.1}\" fill=\"{}\" font-family=\"sans-serif\" font-size=\"14\">{}</text>",
+                    color(*rgb),
+                    escape(text)
+                ),
+            };
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    // RGB rows, top to bottom
+    pub fn to_pixels(&self) -> Vec<u8> {
+        let size = IMAGE_SIZE as usize;
+        let mut pixels: Vec<u8> = [BACKGROUND.0, BACKGROUND.1, BACKGROUND.2].repeat(size * size);
+        let mut blend = |x: i64, y: i64, (r, g, b): Rgb, opacity: f32| {
+            if x < 0 || y < 0 || x >= size as i64 || y >= size as i64 {
+                return;
+            }
+            let index = (y as usize * size + x as usize) * 3;
+            for (channel, value) in pixels[index..index + 3].iter_mut().zip([r, g, b]) {
+                *channel = (*channel as f32 * (1.0 - opacity) + value as f32 * opacity) as u8;
+            }
+        };
+        for shape in &self.shapes {
+            match shape {
+                Shape::Line(points, rgb) => {
+                    for pair in points.windows(2) {
+                        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                        let steps = (x1 - x0).abs().max((y1 - y0).abs()).ceil().max(1.0);
+                        for step in 0..=steps as usize {
+                            let t = step as f32 / steps;
+                            let (x, y) = (x0 + (x1 - x0) * t, y0 + (y1 - y0) * t);
+                            for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                                blend(x as i64 + dx, y as i64 + dy, *rgb, 1.0);
+                            }
+                        }
+                    }
+                }
+                Shape::Rect(x, y, width, height, rgb, opacity) => {
+                    for py in *y as i64..(y + height).ceil() as i64 {
+                        for px in *x as i64..(x + width).ceil() as i64 {
+                            blend(px, py, *rgb, *opacity);
+                        }
+                    }
+                }
+                Shape::Text(x, y, text, rgb) => {
+                    let top = *y as i64 - 7 * GLYPH_SCALE;
+                    for (index, c) in text.chars().enumerate() {
+                        let left = *x as i64 + index as i64 * 6 * GLYPH_SCALE;
+                        for (row, bits) in (0..).zip(glyph(c)) {
+                            for column in (0..5).filter(|column| bits & 0x10 >> column != 0) {
+                                for (dx, dy) in (0..GLYPH_SCALE)
+                                    .flat_map(|dx| (0..GLYPH_SCALE).map(move |dy| (dx, dy)))
+                                {
+                                    blend(
+                                        left + column * GLYPH_SCALE + dx,
+                                        top + row * GLYPH_SCALE + dy,
+                                        *rgb,
+                                        1.0,
+                                    );
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        pixels
+    }
+}
+
+// 5x7 glyph rows top to bottom, the leftmost pixel in bit 4
+fn glyph(c: char) -> [u8; 7] {
+    match c.to_ascii_uppercase() {
+        'A' => [0x0E, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'B' => [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E],
+        'C' => [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E],
+        'D' => [0x1E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1E],
+        'E' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F],
+        'F' => [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10],
+        'G' => [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F],
+        'H' => [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11],
+        'I' => [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        'J' => [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C],
+        'K' => [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11],
+        'L' => [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F],
+        'M' => [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11],
+        'N' => [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11],
+        'O' => [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'P' => [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10],
+        'Q' => [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D],
+        'R' => [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11],
+        'S' => [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E],
+        'T' => [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04],
+        'U' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E],
+        'V' => [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04],
+        'W' => [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A],
+        'X' => [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11],
+        'Y' => [0x11, 0x11, 0x0A, 0x04, 0x04, 0x04, 0x04],
+        'Z' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F],
+        '0' => [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E],
+        '1' => [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E],
+        '2' => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F],
+        '3' => [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E],
+        '4' => [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02],
+        '5' => [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E],
+        '6' => [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E],
+        '7' => [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08],
+        '8' => [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E],
+        '9' => [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C],
+        ' ' => [0; 7],
+        '-' => [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00],
+        '.' => [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C],
+        '\'' => [0x0C, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00],
+        _ => [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04],
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// One line per player following every position logged for them during the fight
+pub fn movement_traces(tracks: &PositionTracks, fight: &Fight) -> Scene {
+    let traces: Vec<_> = fight
+        .players()
+        .map(|p| {
+            let points: Vec<_> = tracks.track(p.unit_id).iter().map(|p| (p.x, p.y)).collect();
+            (p.name.as_ref(), points)
+        })
+        .collect();
+    let bounds = Bounds::around(traces.iter().flat_map(|(_, points)| points.iter().copied()));
+    let mut scene = Scene::new(format!("{} movement", fight.label()), &bounds, fight.map());
+    for (index, (name, points)) in traces.into_iter().enumerate() {
+        let color = PALETTE[index % PALETTE.len()];
+        scene.shapes.push(Shape::Text(
+            8.0,
+            20.0 + 18.0 * index as f32,
+            name.into(),
+            color,
+        ));
+        scene.shapes.push(Shape::Line(
+            points.into_iter().map(|p| bounds.project(p)).collect(),
+            color,
+        ));
+    }
+    scene
+}
+
+// Damage taken by the group binned by where the player stood when hit
+pub fn damage_heatmap(fight: &Fight) -> Scene {
+    let players: Vec<usize> = fight.players().map(|p| p.unit_id).collect();
+    let mut hits = vec![];
+    for segment in fight.segments {
+        let SegmentType::CombatEvent(event) = &segment.line else {
+            continue;
+        };
+        let Some(target) = event.target.state(&event.source) else {
+            continue;
+        };
+        let (x, y) = target.position();
+        if event.action_result.is_damage()
+            && players.contains(&target.unit_id())
+            && (x != 0.0 || y != 0.0)
+        {
+            hits.push(((x, y), event.hit_value));
+        }
+    }
+
+    let bounds = Bounds::around(hits.iter().map(|(position, _)| *position));
+    let mut cells = vec![0_usize; HEATMAP_CELLS * HEATMAP_CELLS];
+    let cell_size = IMAGE_SIZE as f32 / HEATMAP_CELLS as f32;
+    for (position, damage) in hits {
+        let (x, y) = bounds.project(position);
+        let column = ((x / cell_size) as usize).min(HEATMAP_CELLS - 1);
+        let row = ((y / cell_size) as usize).min(HEATMAP_CELLS - 1);
+        cells[row * HEATMAP_CELLS + column] += damage;
+    }
+
+    let mut scene = Scene::new(
+        format!("{} damage taken", fight.label()),
+        &bounds,
+        fight.map(),
+    );
+    let max = cells.iter().copied().max().unwrap_or_default().max(1) as f32;
+    for (index, damage) in cells.into_iter().enumerate() {
+        if damage == 0 {
+            continue;
+        }
+        // Yellow for light damage through to red for the worst spot
+        let heat = damage as f32 / max;
+        scene.shapes.push(Shape::Rect(
+            (index % HEATMAP_CELLS) as f32 * cell_size,
+            (index / HEATMAP_CELLS) as f32 * cell_size,
+            cell_size,
+            cell_size,
+            (255, (255.0 * (1.0 - heat)) as u8, 0),
+            0.3 + 0.7 * heat,
+        ));
+    }
+    scene
+}